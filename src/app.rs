@@ -48,6 +48,24 @@ pub(crate) fn format_weight(w: f64) -> String {
     else { format!("{:.1}", w) }
 }
 
+/// Stored kg value formatted in the user's unit (number only)
+pub(crate) fn format_weight_in(kg: f64, unit: crate::types::WeightUnit) -> String {
+    format_weight((unit.in_unit(kg) * 100.0).round() / 100.0)
+}
+
+/// Stored kg value formatted in the user's unit, with the unit label
+pub(crate) fn display_weight(kg: f64, unit: crate::types::WeightUnit) -> String {
+    format!("{} {}", format_weight_in(kg, unit), unit.label())
+}
+
+/// A record's value: lifted weights as entered, estimates and volume to the nearest whole unit
+pub(crate) fn format_pr_value(pr: &crate::stats::PersonalRecord, unit: crate::types::WeightUnit) -> String {
+    match pr.kind {
+        crate::stats::PrKind::RepMax(_) => display_weight(pr.value, unit),
+        _ => format!("{:.0} {}", unit.in_unit(pr.value), unit.label()),
+    }
+}
+
 pub(crate) fn parse_target_range(target: &str) -> (u8, u8) {
     if target.contains("AMRAP") { return (8, 15); }
     if let Some(dash) = target.find('-') {
@@ -64,6 +82,34 @@ pub(crate) fn parse_target_reps(target: &str) -> u8 {
     parse_target_range(target).0
 }
 
+/// Seconds in a timed target: "45", "45s", "45 sek", "5 min" or "2:30"
+pub(crate) fn parse_duration(target: &str) -> Option<u32> {
+    let t = target.trim().to_lowercase();
+    if let Some((m, s)) = t.split_once(':') {
+        return Some(m.trim().parse::<u32>().ok()? * 60 + s.trim().parse::<u32>().ok()?);
+    }
+    let digits: String = t.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let n: u32 = digits.parse().ok()?;
+    Some(if t.contains("min") { n * 60 } else { n })
+}
+
+/// Pace as "5:12 /km"
+pub(crate) fn format_pace(secs_per_km: f64) -> String {
+    let secs = secs_per_km.round() as u32;
+    format!("{}:{:02} /km", secs / 60, secs % 60)
+}
+
+/// "45s" under a minute, "5 min" on whole minutes, otherwise "2:30"
+pub(crate) fn format_duration(secs: u32) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs.is_multiple_of(60) {
+        format!("{} min", secs / 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
 #[component]
 pub fn App() -> impl IntoView {
     // Check if user is already logged in
@@ -84,8 +130,10 @@ pub fn App() -> impl IntoView {
                 AppView::Dashboard => view! { <Dashboard set_view=set_view auth=auth /> }.into_view(),
                 AppView::Workout(routine) => view! { <Workout routine=routine set_view=set_view /> }.into_view(),
                 AppView::Stats => view! { <Stats set_view=set_view set_auth=set_auth /> }.into_view(),
+                AppView::History => view! { <History set_view=set_view /> }.into_view(),
                 AppView::Settings => view! { <Settings set_view=set_view auth=auth set_auth=set_auth /> }.into_view(),
                 AppView::RoutineBuilder(id) => view! { <RoutineBuilder routine_id=id set_view=set_view /> }.into_view(),
+                AppView::ExerciseDetail(id, resume) => view! { <ExerciseDetail exercise_id=id resume=resume set_view=set_view /> }.into_view(),
             }}
         </div>
     }
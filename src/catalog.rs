@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+/// A known exercise: stable id, the name shown by default and every other name it goes by
+pub struct CatalogEntry {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+}
+
+/// Built-in exercises with their English and Swedish names.
+/// Ids never change, so history keeps following an exercise whatever it's called.
+pub const CATALOG: &[CatalogEntry] = &[
+    CatalogEntry { id: "squat", name: "Squats", aliases: &["Squat", "Back Squat", "Barbell Squat", "Knäböj", "Knäböj med skivstång"] },
+    CatalogEntry { id: "front_squat", name: "Front Squat", aliases: &["Front Squats", "Frontböj"] },
+    CatalogEntry { id: "deadlift", name: "Deadlift", aliases: &["Deadlifts", "Conventional Deadlift", "Marklyft"] },
+    CatalogEntry { id: "romanian_deadlift", name: "Romanian Deadlift", aliases: &["RDL", "Rumänsk marklyft", "Raka ben-marklyft"] },
+    CatalogEntry { id: "bench_press", name: "Bench Press", aliases: &["Bench", "Barbell Bench Press", "Bänkpress", "Bänk"] },
+    CatalogEntry { id: "incline_bench_press", name: "Incline Bench Press", aliases: &["Incline Press", "Lutande bänkpress"] },
+    CatalogEntry { id: "shoulder_press", name: "Shoulder Press", aliases: &["Overhead Press", "OHP", "Military Press", "Militärpress", "Axelpress", "Stående axelpress"] },
+    CatalogEntry { id: "hip_thrust", name: "Hip Thrusts", aliases: &["Hip Thrust", "Barbell Hip Thrust", "Höftlyft"] },
+    CatalogEntry { id: "lat_pulldown", name: "Latsdrag", aliases: &["Lat Pulldown", "Lat Pulldowns", "Pulldown"] },
+    CatalogEntry { id: "pull_up", name: "Pull-ups", aliases: &["Pull-up", "Pullups", "Chins", "Chin-ups", "Räckhäv"] },
+    CatalogEntry { id: "barbell_row", name: "Barbell Row", aliases: &["Bent Over Row", "Skivstångsrodd", "Stångrodd"] },
+    CatalogEntry { id: "seated_row", name: "Sittande rodd", aliases: &["Seated Row", "Seated Cable Row", "Cable Row", "Kabelrodd"] },
+    CatalogEntry { id: "leg_press", name: "Leg Press", aliases: &["Benpress"] },
+    CatalogEntry { id: "leg_curl", name: "Leg Curls", aliases: &["Leg Curl", "Lying Leg Curl", "Lårcurl", "Bencurl"] },
+    CatalogEntry { id: "leg_extension", name: "Leg Extensions", aliases: &["Leg Extension", "Bensträck", "Benspark"] },
+    CatalogEntry { id: "dips", name: "Dips", aliases: &["Dip", "Parallel Bar Dips"] },
+    CatalogEntry { id: "standing_calf_raise", name: "Stående vadpress", aliases: &["Standing Calf Raise", "Standing Calf Raises", "Calf Raise"] },
+    CatalogEntry { id: "seated_calf_raise", name: "Sittande vadpress", aliases: &["Seated Calf Raise", "Seated Calf Raises"] },
+    CatalogEntry { id: "lateral_raise", name: "Sidolyft", aliases: &["Lateral Raise", "Lateral Raises", "Side Raise"] },
+    CatalogEntry { id: "hammer_curl", name: "Hammercurls", aliases: &["Hammer Curl", "Hammer Curls", "Hammercurl"] },
+    CatalogEntry { id: "biceps_curl", name: "Bicepscurl", aliases: &["Biceps Curl", "Bicep Curl", "Curls", "Barbell Curl", "Skivstångscurl"] },
+    CatalogEntry { id: "triceps_pushdown", name: "Triceps Pushdown", aliases: &["Pushdown", "Tricepspress", "Triceps i kabel"] },
+    CatalogEntry { id: "face_pull", name: "Facepulls", aliases: &["Face Pull", "Face Pulls", "Facepull"] },
+    CatalogEntry { id: "lunge", name: "Utfallssteg", aliases: &["Lunge", "Lunges", "Walking Lunges"] },
+    CatalogEntry { id: "push_up", name: "Armhävningar", aliases: &["Push-up", "Push-ups", "Pushups"] },
+    CatalogEntry { id: "plank", name: "Plankan", aliases: &["Plank", "Planka"] },
+    CatalogEntry { id: "dead_bug", name: "Dead Bug", aliases: &["Dead Bugs"] },
+    CatalogEntry { id: "shoulder_taps", name: "Shoulder Taps", aliases: &["Shoulder Tap", "Axelklapp"] },
+    CatalogEntry { id: "mountain_climbers", name: "Mountain Climbers", aliases: &["Mountain Climber", "Bergsklättrare"] },
+    CatalogEntry { id: "rowing", name: "Rodd (maskin)", aliases: &["Rowing", "Rower", "Row Erg", "Roddmaskin"] },
+    CatalogEntry { id: "running", name: "Löpning", aliases: &["Running", "Run", "Treadmill", "Löpband"] },
+    CatalogEntry { id: "cycling", name: "Cykel", aliases: &["Cycling", "Bike", "Spinning", "Motionscykel"] },
+];
+
+/// Catalog lifts done with a barbell, for exercises without Wger equipment
+pub const BARBELL_LIFTS: &[&str] = &[
+    "squat", "front_squat", "deadlift", "romanian_deadlift", "bench_press",
+    "incline_bench_press", "shoulder_press", "hip_thrust", "barbell_row",
+];
+
+/// Case, spacing and punctuation don't make a different exercise.
+/// Underscores are kept so an id normalizes (and slugs) to itself.
+pub fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '_')
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Id for an exercise outside the catalog, derived from the name it was first logged under
+pub fn slug(name: &str) -> String {
+    normalize(name).replace(' ', "_")
+}
+
+pub fn lookup(name: &str) -> Option<&'static CatalogEntry> {
+    let key = normalize(name);
+    CATALOG.iter().find(|e| {
+        normalize(e.name) == key || e.aliases.iter().any(|a| normalize(a) == key)
+    })
+}
+
+/// Stable id for a name: the user's own aliases first, then the catalog, then a slug
+pub fn exercise_id(name: &str, aliases: &HashMap<String, String>) -> String {
+    if let Some(id) = aliases.get(&normalize(name)) {
+        return id.clone();
+    }
+    lookup(name).map(|e| e.id.to_string()).unwrap_or_else(|| slug(name))
+}
+
+/// Default name for a catalog id
+pub fn display_name(id: &str) -> Option<&'static str> {
+    CATALOG.iter().find(|e| e.id == id).map(|e| e.name)
+}
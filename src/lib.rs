@@ -3,6 +3,9 @@ mod types;
 mod storage;
 mod supabase;
 mod stats;
+mod plates;
+mod wger;
+mod catalog;
 mod pages;
 
 use wasm_bindgen::prelude::*;
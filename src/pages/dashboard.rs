@@ -1,5 +1,6 @@
 use leptos::*;
-use crate::types::{AppView, AuthSession};
+use crate::types::{AppView, AuthSession, Exercise, WeightUnit};
+use crate::stats::{PlateauKind, DELOAD_FACTOR, REGRESSION_SESSIONS};
 use crate::storage;
 use crate::supabase;
 use crate::app::{format_time, format_date};
@@ -18,6 +19,7 @@ pub fn Dashboard(set_view: WriteSignal<AppView>, auth: ReadSignal<Option<AuthSes
 
     let (data_version, set_data_version) = create_signal(storage::get_data_version());
     let (is_loading, set_is_loading) = create_signal(true);
+    let unit = storage::load_weight_unit();
 
     let (active_routine, set_active_routine) = create_signal(Option::<crate::types::SavedRoutine>::None);
     let (routine_loading, set_routine_loading) = create_signal(true);
@@ -74,6 +76,36 @@ pub fn Dashboard(set_view: WriteSignal<AppView>, auth: ReadSignal<Option<AuthSes
             .unwrap_or_default()
     };
 
+    // Stalled or regressing exercises in the active routine, each with a swap candidate
+    let plateaus = create_memo(move |_| {
+        let _ = data_version.get();
+        let Some(routine) = active_routine.get() else { return vec![] };
+        let db = storage::load_data();
+        let in_routine: Vec<String> = routine.passes.iter()
+            .flat_map(|p| p.exercises.iter().chain(p.finishers.iter()))
+            .map(|e| db.id_of(e))
+            .collect();
+        storage::routine_plateaus(&routine, &db).into_iter()
+            .map(|(ex, plateau)| {
+                let swap = storage::find_alternatives(&ex, &db).into_iter()
+                    .find(|alt| !in_routine.contains(&db.id_of(alt)));
+                (ex, plateau, swap)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let refresh = move || set_data_version.update(|v| *v += 1);
+
+    let swap_exercise = move |old: Exercise, replacement: Exercise| {
+        if let Some(routine) = storage::swap_in_active_routine(&old, &replacement) {
+            set_active_routine.set(Some(routine.clone()));
+            spawn_local(async move {
+                let _ = supabase::save_routine(&routine).await;
+            });
+        }
+        refresh();
+    };
+
     let (show_confirm, set_show_confirm) = create_signal(false);
     let (pending_pass, set_pending_pass) = create_signal(String::new());
 
@@ -111,8 +143,8 @@ pub fn Dashboard(set_view: WriteSignal<AppView>, auth: ReadSignal<Option<AuthSes
                     <span class="quick-stat-label">"pass"</span>
                 </div>
                 <div class="quick-stat">
-                    <span class="quick-stat-value">{move || format!("{:.0}", stats.get().0.total_volume / 1000.0)}</span>
-                    <span class="quick-stat-label">"ton"</span>
+                    <span class="quick-stat-value">{move || format!("{:.0}", unit.in_unit(stats.get().0.total_volume) / 1000.0)}</span>
+                    <span class="quick-stat-label">{if unit == WeightUnit::Lb { "k lb" } else { "ton" }}</span>
                 </div>
             </div>
 
@@ -184,6 +216,56 @@ pub fn Dashboard(set_view: WriteSignal<AppView>, auth: ReadSignal<Option<AuthSes
                 }
             }}
 
+            {move || {
+                let list = plateaus.get();
+                (!list.is_empty()).then(|| view! {
+                    <div class="plateau-card">
+                        <div class="recent-title">"Platåer"</div>
+                        {list.into_iter().map(|(ex, plateau, swap)| {
+                            let status = match plateau.kind {
+                                PlateauKind::Stalled { weeks } => format!("Ingen ny E1RM på {} veckor", weeks),
+                                PlateauKind::Regressing { drop_pct } => format!("Nedåt {} pass i rad (−{:.0}%)", REGRESSION_SESSIONS, drop_pct),
+                            };
+                            let ex_deload = ex.clone();
+                            let ex_swap = ex.clone();
+                            view! {
+                                <div class="plateau-item">
+                                    <span class="plateau-name">{ex.name.clone()}</span>
+                                    <span class="plateau-status">{status}</span>
+                                    <div class="plateau-actions">
+                                        <button class="plateau-btn" on:click=move |_| {
+                                            if let Some(routine) = storage::apply_deload(&ex_deload) {
+                                                set_active_routine.set(Some(routine.clone()));
+                                                spawn_local(async move {
+                                                    let _ = supabase::save_routine(&routine).await;
+                                                });
+                                            }
+                                            refresh();
+                                        }>
+                                            {format!("Deload −{:.0}%", (1.0 - DELOAD_FACTOR) * 100.0)}
+                                        </button>
+                                        {swap.map(|alt| {
+                                            let label = format!("Byt till {}", alt.name);
+                                            view! {
+                                                <button class="plateau-btn" on:click=move |_| swap_exercise(ex_swap.clone(), alt.clone())>
+                                                    {label}
+                                                </button>
+                                            }
+                                        })}
+                                        <button class="plateau-btn dismiss" on:click=move |_| {
+                                            storage::dismiss_plateau(&plateau);
+                                            refresh();
+                                        }>
+                                            "Ignorera"
+                                        </button>
+                                    </div>
+                                </div>
+                            }
+                        }).collect_view()}
+                    </div>
+                })
+            }}
+
             {move || {
                 let recent = stats.get().1;
                 let active = active_routine.get();
@@ -196,7 +278,7 @@ pub fn Dashboard(set_view: WriteSignal<AppView>, auth: ReadSignal<Option<AuthSes
                                 .unwrap_or(0);
                             let color_class = format!("pass-{}", (b'a' + pass_idx as u8) as char);
                             view! {
-                                <div class="recent-item">
+                                <div class="recent-item" on:click=move |_| set_view.set(AppView::History)>
                                     <span class=format!("recent-routine {}", color_class)>{&s.routine}</span>
                                     <span class="recent-date">{format_date(s.timestamp)}</span>
                                     <span class="recent-duration">{format_time(s.duration_secs)}</span>
@@ -207,9 +289,14 @@ pub fn Dashboard(set_view: WriteSignal<AppView>, auth: ReadSignal<Option<AuthSes
                 })
             }}
 
-            <button class="stats-link" on:click=move |_| set_view.set(AppView::Stats)>
-                "Statistik →"
-            </button>
+            <div class="dashboard-links">
+                <button class="stats-link" on:click=move |_| set_view.set(AppView::History)>
+                    "Historik →"
+                </button>
+                <button class="stats-link" on:click=move |_| set_view.set(AppView::Stats)>
+                    "Statistik →"
+                </button>
+            </div>
 
             <div class="logged-in-info">
                 "inloggad: "{move || user_display()}<br/>
@@ -0,0 +1,124 @@
+use leptos::*;
+use crate::types::{AppView, SetKind};
+use crate::storage;
+use crate::stats;
+use crate::app::{display_weight, format_date, format_duration, format_weight_in};
+use super::stats_page::TrendChart;
+
+/// One exercise's whole history: progress charts, rep PRs and every session's sets
+#[component]
+pub fn ExerciseDetail(exercise_id: String, resume: Option<String>, set_view: WriteSignal<AppView>) -> impl IntoView {
+    let unit = storage::load_weight_unit();
+    let formula = storage::load_e1rm_formula();
+    let db = storage::load_data();
+    let name = db.exercise_name(&exercise_id);
+
+    let mut sessions: Vec<_> = db.sessions.iter()
+        .filter_map(|s| s.exercise(&exercise_id).map(|e| (s.timestamp, e.clone())))
+        .collect();
+    sessions.sort_by_key(|(ts, _)| *ts);
+
+    let e1rm_points: Vec<(i64, f64)> = sessions.iter()
+        .map(|(ts, e)| (*ts, e.rep_sets().map(|s| formula.of_set(s)).fold(0.0, f64::max)))
+        .filter(|(_, e1rm)| *e1rm > 0.0)
+        .map(|(ts, e1rm)| (ts, unit.in_unit(e1rm)))
+        .collect();
+    let volumes: Vec<(i64, f64)> = sessions.iter()
+        .map(|(ts, e)| (*ts, e.sets.iter().map(|s| s.volume()).sum::<f64>()))
+        .filter(|(_, v)| *v > 0.0)
+        .collect();
+    let rep_bests = stats::rep_bests(&db, &exercise_id);
+
+    let go_back = move |_| {
+        set_view.set(match resume.clone() {
+            Some(pass) => {
+                storage::return_from_history();
+                AppView::Workout(pass)
+            }
+            None => AppView::Stats,
+        });
+    };
+
+    view! {
+        <div class="stats">
+            <div class="stats-header">
+                <button class="stats-back-btn" on:click=go_back>"←"</button>
+                <div class="stats-title">{name}</div>
+                <div class="stats-back-btn"></div>
+            </div>
+
+            <div class="stats-body">
+                {sessions.is_empty().then(|| view! { <div class="history-empty">"Inga pass med övningen ännu"</div> })}
+
+                {(e1rm_points.len() >= 2).then(|| view! {
+                    <div class="stat-card">
+                        <div class="stat-card-title">"E1RM"</div>
+                        <div class="stat-card-hint">{format!("Bästa set per pass ({}), {}", formula.label(), unit.label())}</div>
+                        <TrendChart points=e1rm_points />
+                    </div>
+                })}
+
+                {(!volumes.is_empty()).then(|| {
+                    let max = volumes.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+                    let latest = volumes.last().map(|(_, v)| *v).unwrap_or(0.0);
+                    view! {
+                        <div class="stat-card">
+                            <div class="stat-card-title">"Volym per pass"</div>
+                            <div class="stat-card-hint">{format!("Senast {:.0} {}", unit.in_unit(latest), unit.label())}</div>
+                            <div class="power-chart">
+                                {volumes.iter().rev().take(12).rev().map(|(_, v)| view! {
+                                    <div class="power-bar" style=format!("height: {}%", (v / max * 100.0).max(8.0))></div>
+                                }).collect_view()}
+                            </div>
+                        </div>
+                    }
+                })}
+
+                {(!rep_bests.is_empty()).then(|| view! {
+                    <div class="stat-card">
+                        <div class="stat-card-title">"Rekord per antal reps"</div>
+                        <div class="rep-best-list">
+                            {rep_bests.into_iter().map(|(reps, weight, ts)| view! {
+                                <div class="rep-best-row">
+                                    <span class="rep-best-reps">{format!("{}RM", reps)}</span>
+                                    <span class="rep-best-weight">{display_weight(weight, unit)}</span>
+                                    <span class="rep-best-date">{format_date(ts)}</span>
+                                </div>
+                            }).collect_view()}
+                        </div>
+                    </div>
+                })}
+
+                {sessions.into_iter().rev().map(|(ts, record)| {
+                    let volume: f64 = record.sets.iter().map(|s| s.volume()).sum();
+                    view! {
+                        <div class="stat-card history-exercise">
+                            <div class="history-exercise-header">
+                                <span class="history-exercise-name">{format_date(ts)}</span>
+                                {(volume > 0.0).then(|| view! {
+                                    <span class="history-item-summary">{display_weight(volume, unit)}</span>
+                                })}
+                            </div>
+                            <div class="overview-set-list history-set-list">
+                                {record.sets.iter().enumerate().map(|(i, set)| {
+                                    let row_class = if set.kind == SetKind::Warmup { "overview-set warmup" } else { "overview-set" };
+                                    let text = match (set.duration_secs, set.weight > 0.0) {
+                                        (Some(secs), _) => format_duration(secs),
+                                        (None, true) => format!("{} {} × {}", format_weight_in(set.weight, unit), unit.label(), set.reps),
+                                        (None, false) => format!("{} reps", set.reps),
+                                    };
+                                    view! {
+                                        <div class=row_class>
+                                            <span class="overview-set-num">{i + 1}</span>
+                                            <span class="overview-set-unit">{text}</span>
+                                        </div>
+                                    }
+                                }).collect_view()}
+                            </div>
+                        </div>
+                    }
+                }).collect_view()}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,243 @@
+use leptos::*;
+use crate::types::{AppView, Session, SetKind, SetRecord};
+use crate::storage;
+use crate::app::{format_time, format_date, format_weight_in, display_weight};
+
+#[component]
+pub fn History(set_view: WriteSignal<AppView>) -> impl IntoView {
+    let unit = storage::load_weight_unit();
+    let (data_version, set_data_version) = create_signal(0u32);
+    // Session being edited; changes stay in the draft until saved
+    let (draft, set_draft) = create_signal(Option::<Session>::None);
+    let (show_delete_confirm, set_show_delete_confirm) = create_signal(false);
+
+    // Which session is open and how many sets each exercise has. Only these
+    // re-render the editor; typed values live in the inputs and the draft.
+    let draft_shape = create_memo(move |_| draft.with(|d| d.as_ref().map(|s| {
+        (s.id.clone(), s.exercises.iter().map(|e| e.sets.len()).collect::<Vec<_>>())
+    })));
+
+    let sessions = move || {
+        let _ = data_version.get();
+        let db = storage::load_data();
+        db.get_recent_sessions(db.sessions.len())
+    };
+
+    let edit_set = move |ex_idx: usize, set_idx: usize, edit: &dyn Fn(&mut SetRecord)| {
+        set_draft.update(|d| {
+            if let Some(set) = d.as_mut()
+                .and_then(|s| s.exercises.get_mut(ex_idx))
+                .and_then(|e| e.sets.get_mut(set_idx))
+            {
+                edit(set);
+            }
+        });
+    };
+
+    let delete_set = move |ex_idx: usize, set_idx: usize| {
+        set_draft.update(|d| {
+            if let Some(ex) = d.as_mut().and_then(|s| s.exercises.get_mut(ex_idx)) {
+                if set_idx < ex.sets.len() {
+                    ex.sets.remove(set_idx);
+                }
+            }
+        });
+    };
+
+    let delete_exercise = move |ex_idx: usize| {
+        set_draft.update(|d| {
+            if let Some(s) = d.as_mut() {
+                if ex_idx < s.exercises.len() {
+                    s.exercises.remove(ex_idx);
+                }
+            }
+        });
+    };
+
+    let close_detail = move || {
+        set_show_delete_confirm.set(false);
+        set_draft.set(None);
+        set_data_version.update(|v| *v += 1);
+    };
+
+    let save_draft = move |_| {
+        if let Some(session) = draft.get_untracked() {
+            // Nothing left to keep - saving an empty pass means deleting it
+            if session.exercises.iter().all(|e| e.sets.is_empty()) {
+                storage::delete_session(&session.id);
+            } else {
+                storage::update_session(session);
+            }
+        }
+        close_detail();
+    };
+
+    let confirm_delete = move |_| {
+        if let Some(session) = draft.get_untracked() {
+            storage::delete_session(&session.id);
+        }
+        close_detail();
+    };
+
+    let detail_view = move |session: Session| {
+        // Weight inputs follow the session as saved, so zeroing every weight in the draft can be undone
+        let saved = storage::load_data().sessions.into_iter().find(|s| s.id == session.id);
+        view! {
+            <div class="history-detail-info">
+                <span>{format_date(session.timestamp)}</span>
+                <span>{format_time(session.duration_secs)}</span>
+            </div>
+            {session.exercises.iter().enumerate().map(|(ei, ex)| {
+                let is_bodyweight = saved.as_ref()
+                    .and_then(|s| s.exercise(&ex.exercise_id))
+                    .unwrap_or(ex)
+                    .sets.iter().all(|s| s.weight == 0.0);
+                view! {
+                    <div class="stat-card history-exercise">
+                        <div class="history-exercise-header">
+                            <span class="history-exercise-name">{ex.name.clone()}</span>
+                            <button class="history-exercise-delete" on:click=move |_| delete_exercise(ei)>
+                                "Ta bort"
+                            </button>
+                        </div>
+                        <div class="overview-set-list history-set-list">
+                            {ex.sets.iter().enumerate().map(|(si, set)| {
+                                let row_class = if set.kind == SetKind::Warmup { "overview-set warmup" } else { "overview-set" };
+                                view! {
+                                    <div class=row_class>
+                                        <span class="overview-set-num">{si + 1}</span>
+                                        {(!is_bodyweight).then(|| view! {
+                                            <input type="number" step="0.5" class="overview-set-input"
+                                                value=format_weight_in(set.weight, unit)
+                                                on:change=move |ev| {
+                                                    if let Ok(w) = event_target_value(&ev).replace(',', ".").parse::<f64>() {
+                                                        let kg = unit.to_kg(w).max(0.0);
+                                                        edit_set(ei, si, &|s| s.weight = kg);
+                                                    }
+                                                }
+                                            />
+                                            <span class="overview-set-unit">{unit.label()}" ×"</span>
+                                        })}
+                                        {set.distance_m().map(|m| view! {
+                                            <input type="number" step="0.01" class="overview-set-input"
+                                                value=format!("{:.2}", m / 1000.0)
+                                                on:change=move |ev| {
+                                                    if let Ok(km) = event_target_value(&ev).replace(',', ".").parse::<f64>() {
+                                                        edit_set(ei, si, &|s| {
+                                                            if let Some(c) = s.cardio.as_mut() {
+                                                                c.distance_m = Some(km.max(0.0) * 1000.0);
+                                                            }
+                                                        });
+                                                    }
+                                                }
+                                            />
+                                            <span class="overview-set-unit">"km"</span>
+                                        })}
+                                        {match set.duration_secs {
+                                            Some(secs) => view! {
+                                                <input type="number" class="overview-set-input"
+                                                    value=secs.to_string()
+                                                    on:change=move |ev| {
+                                                        if let Ok(d) = event_target_value(&ev).parse::<u32>() {
+                                                            edit_set(ei, si, &|s| s.duration_secs = Some(d));
+                                                        }
+                                                    }
+                                                />
+                                                <span class="overview-set-unit">"s"</span>
+                                            }.into_view(),
+                                            None => view! {
+                                                <input type="number" class="overview-set-input"
+                                                    value=set.reps.to_string()
+                                                    on:change=move |ev| {
+                                                        if let Ok(r) = event_target_value(&ev).parse::<u8>() {
+                                                            edit_set(ei, si, &|s| s.reps = r);
+                                                        }
+                                                    }
+                                                />
+                                                <span class="overview-set-unit">"reps"</span>
+                                            }.into_view(),
+                                        }}
+                                        <button class="overview-set-delete" title="Ta bort set" on:click=move |_| delete_set(ei, si)>"✕"</button>
+                                    </div>
+                                }
+                            }).collect_view()}
+                        </div>
+                    </div>
+                }
+            }).collect_view()}
+            <div class="history-actions">
+                <button class="history-save-btn" on:click=save_draft>"Spara ändringar"</button>
+                <button class="history-delete-btn" on:click=move |_| set_show_delete_confirm.set(true)>
+                    "Radera pass"
+                </button>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="stats history">
+            <div class="stats-header">
+                <button class="stats-back-btn" on:click=move |_| {
+                    if draft.get_untracked().is_some() {
+                        close_detail();
+                    } else {
+                        set_view.set(AppView::Dashboard);
+                    }
+                }>
+                    "←"
+                </button>
+                <div class="stats-title">
+                    {move || draft.with(|d| d.as_ref().map(|s| s.routine.clone()).unwrap_or_else(|| "Historik".to_string()))}
+                </div>
+                <div class="stats-back-btn"></div>
+            </div>
+
+            <div class="stats-body">
+                {move || {
+                    draft_shape.track();
+                    match draft.get_untracked() {
+                        Some(session) => detail_view(session).into_view(),
+                        None => {
+                            let list = sessions();
+                            if list.is_empty() {
+                                view! { <div class="history-empty">"Inga pass ännu"</div> }.into_view()
+                            } else {
+                                list.into_iter().map(|s| {
+                                    let set_count: usize = s.exercises.iter().map(|e| e.sets.len()).sum();
+                                    let summary = format!("{} set · {}", set_count, display_weight(s.total_volume, unit));
+                                    let date = format_date(s.timestamp);
+                                    let duration = format_time(s.duration_secs);
+                                    let routine = s.routine.clone();
+                                    view! {
+                                        <button class="history-item" on:click=move |_| set_draft.set(Some(s.clone()))>
+                                            <span class="history-item-routine">{routine}</span>
+                                            <span class="history-item-date">{date}" · "{duration}</span>
+                                            <span class="history-item-summary">{summary}</span>
+                                        </button>
+                                    }
+                                }).collect_view()
+                            }
+                        }
+                    }
+                }}
+            </div>
+
+            {move || show_delete_confirm.get().then(|| view! {
+                <div class="modal-overlay">
+                    <div class="confirm-dialog">
+                        <div class="confirm-title">"Radera pass?"</div>
+                        <div class="confirm-text">"Passet tas bort här och i molnet. Det går inte att ångra."</div>
+                        <div class="confirm-buttons">
+                            <button class="confirm-cancel" on:click=move |_| set_show_delete_confirm.set(false)>
+                                "Avbryt"
+                            </button>
+                            <button class="confirm-ok" on:click=confirm_delete>
+                                "Radera"
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            })}
+        </div>
+    }
+}
@@ -2,12 +2,16 @@ mod auth;
 mod dashboard;
 mod workout;
 mod stats_page;
+mod history;
 mod settings;
 mod routine_builder;
+mod exercise_detail;
 
 pub use auth::{Login, Register};
 pub use dashboard::Dashboard;
 pub use workout::Workout;
 pub use stats_page::Stats;
+pub use history::History;
 pub use settings::Settings;
 pub use routine_builder::RoutineBuilder;
+pub use exercise_detail::ExerciseDetail;
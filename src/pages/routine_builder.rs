@@ -1,10 +1,8 @@
 use leptos::*;
 use serde::{Serialize, Deserialize};
-use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::JsFuture;
-use web_sys::Response;
-use crate::types::AppView;
+use crate::types::{AppView, BlockFormat, group_label};
 use crate::storage;
+use crate::wger::{search_wger_exercises, WgerExercise};
 
 #[derive(Serialize, Deserialize, Debug)]
 struct AiRoutineResponse {
@@ -27,9 +25,10 @@ The JSON must strictly follow this structure:
           "name": "Exercise Name",
           "sets": 3,
           "reps_target": "8-10",
-          "is_superset": false,
-          "superset_with": null,
-          "is_bodyweight": false
+          "group": null,
+          "is_bodyweight": false,
+          "is_cardio": false,
+          "block": null
         }
       ],
       "finishers": []
@@ -38,159 +37,22 @@ The JSON must strictly follow this structure:
 }
 
 Rules:
-1. 'reps_target' can be "5", "8-10", "12-15", or "30 sek" for timed exercises.
-2. SUPERSETS - IMPORTANT:
-   - A superset is TWO exercises performed back-to-back with no rest between them.
-   - Only use supersets to SAVE TIME in passes with 5+ exercises.
-   - Pair exercises for ANTAGONIST muscles (biceps+triceps, chest+back, quads+hamstrings).
-   - If user doesn't want supersets, set is_superset: false for ALL exercises.
-   - If you DO create a superset, BOTH exercises MUST be marked:
-     * Exercise A: "is_superset": true, "superset_with": "Exercise B"
-     * Exercise B: "is_superset": true, "superset_with": "Exercise A"
-   - Never create a superset with only ONE exercise marked!
+1. 'reps_target' can be "5", "8-10", "12-15", or "30 sek" / "3 min" for timed exercises.
+2. GROUPS (supersets, tri-sets, circuits) - IMPORTANT:
+   - A group is 2 or more exercises performed back-to-back: one set of each, then rest, for a number of rounds.
+   - 2 exercises = superset, 3 = tri-set, 4 or more = circuit.
+   - Only use groups to SAVE TIME in passes with 5+ exercises, or for circuit-style conditioning.
+   - Pair supersets for ANTAGONIST muscles (biceps+triceps, chest+back, quads+hamstrings).
+   - Give every member the same "group" letter ("A", "B", ...) and list the members next to each other.
+   - 'sets' is the number of rounds and must be the same for every member of a group.
+   - If user doesn't want supersets, set "group": null for ALL exercises.
+   - Never create a group with only ONE exercise!
 3. 'name' in 'passes' must be max 8 chars (e.g., "PASS A", "BEN", "PUSH").
 4. Each pass should have 4-8 exercises depending on session duration requested.
-5. RESPOND ONLY WITH THE RAW JSON. NO MARKDOWN OR EXPLANATIONS."#;
-
-// Wger API types
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct WgerExercise {
-    id: u32,
-    base_id: u32,
-    name: String,
-    primary_muscles: Vec<String>,
-    secondary_muscles: Vec<String>,
-    image_url: Option<String>,
-    equipment: Option<String>,
-}
-
-// Wger muscle ID → English name mapping
-// Source: https://wger.de/api/v2/muscle/?format=json
-fn wger_muscle_name(id: u32) -> &'static str {
-    match id {
-        1 => "Biceps brachii",
-        2 => "Anterior deltoid",
-        3 => "Serratus anterior",
-        4 => "Pectoralis major",
-        5 => "Obliquus externus abdominis",
-        6 => "Rectus abdominis",
-        7 => "Gastrocnemius",
-        8 => "Gluteus maximus",
-        9 => "Trapezius",
-        10 => "Quadriceps femoris",
-        11 => "Biceps femoris",
-        12 => "Latissimus dorsi",
-        13 => "Brachialis",
-        14 => "Obliquus externus abdominis",
-        15 => "Soleus",
-        _ => "Unknown",
-    }
-}
-
-async fn fetch_wger_muscles(base_id: u32) -> (Vec<String>, Vec<String>) {
-    let window = match web_sys::window() {
-        Some(w) => w,
-        None => return (vec![], vec![]),
-    };
-
-    let url = format!("https://wger.de/api/v2/exerciseinfo/{}/?format=json", base_id);
-    let resp_value = match JsFuture::from(window.fetch_with_str(&url)).await {
-        Ok(v) => v,
-        Err(_) => return (vec![], vec![]),
-    };
-    let resp: Response = match resp_value.dyn_into() {
-        Ok(r) => r,
-        Err(_) => return (vec![], vec![]),
-    };
-    if !resp.ok() { return (vec![], vec![]); }
-
-    let json = match JsFuture::from(match resp.json() { Ok(j) => j, Err(_) => return (vec![], vec![]) }).await {
-        Ok(j) => j,
-        Err(_) => return (vec![], vec![]),
-    };
-
-    #[derive(Deserialize)]
-    struct WgerMuscle { id: u32 }
-    #[derive(Deserialize)]
-    struct WgerExerciseInfo {
-        muscles: Vec<WgerMuscle>,
-        #[serde(default)]
-        muscles_secondary: Vec<WgerMuscle>,
-    }
-
-    let info: WgerExerciseInfo = match serde_wasm_bindgen::from_value(json) {
-        Ok(i) => i,
-        Err(_) => return (vec![], vec![]),
-    };
-
-    let primary: Vec<String> = info.muscles.iter().map(|m| wger_muscle_name(m.id).to_string()).collect();
-    let secondary: Vec<String> = info.muscles_secondary.iter().map(|m| wger_muscle_name(m.id).to_string()).collect();
-    (primary, secondary)
-}
-
-async fn search_wger_exercises(query: &str) -> Result<Vec<WgerExercise>, JsValue> {
-    let window = web_sys::window().ok_or("no window")?;
-
-    let encoded_query = js_sys::encode_uri_component(query);
-    let url = format!("https://wger.de/api/v2/exercise/search/?language=2&term={}", encoded_query);
-    let resp_value = JsFuture::from(window.fetch_with_str(&url)).await?;
-    let resp: Response = resp_value.dyn_into()?;
-
-    if !resp.ok() {
-        return Ok(vec![]);
-    }
-
-    let json = JsFuture::from(resp.json()?).await?;
-
-    #[derive(Deserialize)]
-    struct WgerSearchResponse {
-        suggestions: Vec<WgerSuggestion>,
-    }
-
-    #[derive(Deserialize)]
-    struct WgerSuggestion {
-        #[allow(dead_code)]
-        value: String,
-        data: WgerSuggestionData,
-    }
-
-    #[derive(Deserialize)]
-    struct WgerSuggestionData {
-        id: u32,
-        base_id: u32,
-        name: String,
-        image: Option<String>,
-    }
-
-    let search_resp: WgerSearchResponse = serde_wasm_bindgen::from_value(json).unwrap_or(WgerSearchResponse { suggestions: vec![] });
-
-    let suggestions: Vec<_> = search_resp.suggestions.into_iter().take(10).collect();
-    let mut exercises = Vec::new();
-
-    for s in suggestions {
-        let image_url = s.data.image.map(|img| {
-            if img.starts_with("http") {
-                img
-            } else {
-                format!("https://wger.de{}", img)
-            }
-        });
-
-        let (primary_muscles, secondary_muscles) = fetch_wger_muscles(s.data.base_id).await;
-
-        exercises.push(WgerExercise {
-            id: s.data.id,
-            base_id: s.data.base_id,
-            name: s.data.name,
-            primary_muscles,
-            secondary_muscles,
-            image_url,
-            equipment: None,
-        });
-    }
-
-    Ok(exercises)
-}
+5. Rowing, bike, running and other conditioning work: set "is_cardio": true, 'sets' = number of intervals and 'reps_target' = distance or time per interval (e.g. "500 m", "20 min").
+6. Clock-driven blocks (only for conditioning or when the user asks for them): set "block" on the exercise, or on the first member of its group, to
+   {"kind": "emom", "minutes": 10}, {"kind": "amrap", "minutes": 12} or {"kind": "interval", "work_secs": 20, "rest_secs": 10, "rounds": 8} (Tabata). Otherwise "block": null.
+7. RESPOND ONLY WITH THE RAW JSON. NO MARKDOWN OR EXPLANATIONS."#;
 
 #[component]
 pub fn RoutineBuilder(
@@ -225,6 +87,7 @@ pub fn RoutineBuilder(
     let (ai_finishers, set_ai_finishers) = create_signal(true);
     let (ai_generating, set_ai_generating) = create_signal(false);
     let (ai_error, set_ai_error) = create_signal(None::<String>);
+    let unit = storage::load_weight_unit();
 
     let is_editing = routine_id.is_some();
     let routine_id_for_save = routine_id.clone();
@@ -239,7 +102,9 @@ pub fn RoutineBuilder(
                     if let Some(r) = routines.into_iter().find(|r| r.id == id_inner) {
                         set_routine_name.set(r.name);
                         set_routine_focus.set(r.focus);
-                        set_passes.set(r.passes);
+                        let mut passes = r.passes;
+                        passes.iter_mut().for_each(crate::types::Pass::normalize_groups);
+                        set_passes.set(passes);
                     }
                 }
                 set_loading.set(false);
@@ -294,6 +159,26 @@ pub fn RoutineBuilder(
         set_passes.set(p);
     };
 
+    // The exercise keeps its id under the new name, so its history follows it
+    let rename_exercise = move |idx: usize, ei: usize, finisher: bool, new_name: String| {
+        let new_name = new_name.trim().to_string();
+        let mut p = passes.get();
+        let Some(pass) = p.get_mut(idx) else { return };
+        let list = if finisher { &mut pass.finishers } else { &mut pass.exercises };
+        let Some(exercise) = list.get_mut(ei) else { return };
+        if new_name.is_empty() || new_name == exercise.name {
+            return;
+        }
+        let mut db = storage::load_data();
+        let id = db.id_of(exercise);
+        exercise.id = Some(id.clone());
+        exercise.name = new_name.clone();
+        if db.add_alias(&new_name, &id) {
+            let _ = storage::save_data(&db);
+        }
+        set_passes.set(p);
+    };
+
     let update_pass_description = move |idx: usize, new_desc: String| {
         let mut p = passes.get();
         if let Some(pass) = p.get_mut(idx) {
@@ -315,14 +200,14 @@ pub fn RoutineBuilder(
         let duration = ai_duration.get();
         let ss = ai_supersets.get();
         let fin = ai_finishers.get();
-        let bw = storage::load_data().get_bodyweight().unwrap_or(80.0);
+        let bw = crate::app::display_weight(storage::load_data().get_bodyweight().unwrap_or(80.0), unit);
 
         let user_prompt = format!(
             "Build a routine with {} unique passes. Goal: {}. \
              Qualitative Context: {}. Body parts to focus on: {}. Training style: {}. \
              Equipment available: {}. Preferred session duration: {}. \
-             Include supersets: {}. Include finishers: {}. \
-             User current bodyweight: {}kg.",
+             Include supersets/circuits: {}. Include finishers: {}. \
+             User current bodyweight: {}.",
             pass_count, focus, desc, areas, style, equip, duration, ss, fin, bw
         );
 
@@ -340,35 +225,20 @@ pub fn RoutineBuilder(
                             match serde_json::from_str::<AiRoutineResponse>(clean_json) {
                                 Ok(mut resp) => {
                                     for pass in &mut resp.passes {
-                                        let valid_supersets: Vec<(String, bool)> = pass.exercises.iter()
-                                            .map(|ex| {
-                                                if !ex.is_superset {
-                                                    return (ex.name.clone(), true);
-                                                }
-                                                if let Some(partner_name) = &ex.superset_with {
-                                                    let partner_valid = pass.exercises.iter()
-                                                        .any(|e| &e.name == partner_name
-                                                             && e.is_superset
-                                                             && e.superset_with.as_ref() == Some(&ex.name));
-                                                    (ex.name.clone(), partner_valid)
-                                                } else {
-                                                    (ex.name.clone(), false)
-                                                }
-                                            })
-                                            .collect();
+                                        // "30 sek" / "3 min" targets are timed exercises (cardio keeps its own target)
+                                        for exercise in pass.exercises.iter_mut().chain(pass.finishers.iter_mut()) {
+                                            let target = exercise.reps_target.to_lowercase();
+                                            if !exercise.is_cardio && exercise.duration_secs.is_none() && (target.contains("sek") || target.contains("min")) {
+                                                exercise.duration_secs = crate::app::parse_duration(&target);
+                                            }
+                                        }
 
-                                        for exercise in &mut pass.exercises {
-                                            if let Some((_, is_valid)) = valid_supersets.iter()
-                                                .find(|(name, _)| name == &exercise.name)
-                                            {
-                                                if !is_valid && exercise.is_superset {
-                                                    web_sys::console::log_1(&format!(
-                                                        "Removing broken superset from '{}'",
-                                                        exercise.name
-                                                    ).into());
-                                                    exercise.is_superset = false;
-                                                    exercise.superset_with = None;
-                                                }
+                                        // Broken or single-member groups are dropped, members end up
+                                        // side by side and every member does the group's rounds
+                                        pass.normalize_groups();
+                                        for i in 0..pass.exercises.len() {
+                                            if let Some(key) = pass.exercises[i].group.clone() {
+                                                pass.exercises[i].sets = pass.group_rounds(&key);
                                             }
                                         }
                                     }
@@ -515,19 +385,31 @@ pub fn RoutineBuilder(
                                         <div class="pass-exercises">
                                             <h3>"Övningar"</h3>
                                             {pass.exercises.iter().enumerate().map(|(ei, ex)| {
-                                                let has_superset = ex.is_superset && ex.superset_with.is_some();
-                                                let superset_info = if has_superset {
-                                                    format!(" ⟷ {}", ex.superset_with.as_ref().unwrap_or(&String::new()))
-                                                } else {
-                                                    String::new()
-                                                };
-                                                let ex_name_for_unlink = ex.name.clone();
+                                                let group = ex.group_key();
+                                                let has_superset = group.is_some();
+                                                // e.g. " Tri-set A · 3 varv"
+                                                let superset_info = group.as_ref().map(|key| {
+                                                    let members = pass.group_members(key).len();
+                                                    format!(" {} {} · {} varv", group_label(members), key, pass.group_rounds(key))
+                                                }).unwrap_or_default();
                                                 let ex_sets = ex.sets.to_string();
                                                 let ex_reps = ex.reps_target.clone();
+                                                let warmup = ex.warmup;
+                                                let is_cardio = ex.is_cardio;
+                                                let block = ex.block;
+                                                let show_load = !ex.is_bodyweight && !is_cardio && ex.duration_secs.is_none();
+                                                let default_step = crate::app::format_weight_in(crate::types::default_weight_step(ex.equipment.as_deref(), unit), unit);
+                                                let fmt_opt = |v: Option<f64>| v.map(|w| crate::app::format_weight_in(w, unit)).unwrap_or_default();
+                                                let (ex_step, ex_min, ex_max) = (fmt_opt(ex.weight_step), fmt_opt(ex.min_weight), fmt_opt(ex.max_weight));
+                                                let ex_rest = ex.rest_secs.map(|s| s.to_string()).unwrap_or_default();
+                                                let default_rest = ex.rest_target_secs().to_string();
                                                 view! {
                                                     <div class={if has_superset { "exercise-item superset" } else { "exercise-item" }}>
                                                         <div class="exercise-main">
-                                                            <span class="exercise-name">{&ex.name}</span>
+                                                            <input type="text" class="exercise-name exercise-name-input" value=ex.name.clone()
+                                                                title="Byt namn – historiken följer med"
+                                                                on:blur=move |e| rename_exercise(idx, ei, false, event_target_value(&e))
+                                                            />
                                                             <div class="exercise-edit">
                                                                 <input type="number" class="sets-input" value=ex_sets
                                                                     on:blur=move |e| {
@@ -554,21 +436,142 @@ pub fn RoutineBuilder(
                                                                         set_passes.set(p);
                                                                     }
                                                                 />
+                                                                {(!is_cardio).then(|| view! {
+                                                                    <button class={if warmup == crate::types::WarmupScheme::Off { "warmup-toggle" } else { "warmup-toggle active" }}
+                                                                        title="Uppvärmningsstege (klicka för att byta)"
+                                                                        on:click=move |_| {
+                                                                            let mut p = passes.get();
+                                                                            if let Some(pass) = p.get_mut(idx) {
+                                                                                if let Some(exercise) = pass.exercises.get_mut(ei) {
+                                                                                    exercise.warmup = exercise.warmup.next();
+                                                                                }
+                                                                            }
+                                                                            set_passes.set(p);
+                                                                        }
+                                                                    >
+                                                                        {format!("UV: {}", warmup.label())}
+                                                                    </button>
+                                                                })}
+                                                                <button class={if is_cardio { "warmup-toggle active" } else { "warmup-toggle" }}
+                                                                    title="Kondition: logga distans och tid i stället för vikt × reps"
+                                                                    on:click=move |_| {
+                                                                        let mut p = passes.get();
+                                                                        if let Some(pass) = p.get_mut(idx) {
+                                                                            if let Some(exercise) = pass.exercises.get_mut(ei) {
+                                                                                exercise.is_cardio = !exercise.is_cardio;
+                                                                                if exercise.is_cardio {
+                                                                                    exercise.warmup = crate::types::WarmupScheme::Off;
+                                                                                    exercise.reps_target = "20 min".to_string();
+                                                                                } else {
+                                                                                    exercise.reps_target = "8-12".to_string();
+                                                                                }
+                                                                            }
+                                                                        }
+                                                                        set_passes.set(p);
+                                                                    }
+                                                                >
+                                                                    "Kondition"
+                                                                </button>
+                                                                {(!is_cardio).then(|| view! {
+                                                                    <button class={if block.is_some() { "warmup-toggle active" } else { "warmup-toggle" }}
+                                                                        title="Kör övningen (och dess grupp) på klockan: EMOM, AMRAP eller intervaller"
+                                                                        on:click=move |_| {
+                                                                            let mut p = passes.get();
+                                                                            if let Some(pass) = p.get_mut(idx) {
+                                                                                if let Some(exercise) = pass.exercises.get_mut(ei) {
+                                                                                    exercise.block = BlockFormat::next(exercise.block);
+                                                                                }
+                                                                            }
+                                                                            set_passes.set(p);
+                                                                        }
+                                                                    >
+                                                                        {block.map(|b| b.label()).unwrap_or_else(|| "Block: Av".to_string())}
+                                                                    </button>
+                                                                })}
+                                                            </div>
+                                                            {block.map(|format| {
+                                                                let update_block = move |val: String, field: usize| {
+                                                                    let Ok(v) = val.parse::<u32>() else { return };
+                                                                    let mut p = passes.get();
+                                                                    if let Some(exercise) = p.get_mut(idx).and_then(|pass| pass.exercises.get_mut(ei)) {
+                                                                        exercise.block = exercise.block.map(|b| b.with_field(field, v));
+                                                                    }
+                                                                    set_passes.set(p);
+                                                                };
+                                                                view! {
+                                                                    <div class="exercise-load-edit">
+                                                                        {format.fields().into_iter().enumerate().map(|(field, (label, value))| view! {
+                                                                            <span class="load-label">{label}</span>
+                                                                            <input type="number" class="load-input" value=value.to_string()
+                                                                                on:blur=move |e| update_block(event_target_value(&e), field)
+                                                                            />
+                                                                        }).collect_view()}
+                                                                    </div>
+                                                                }
+                                                            })}
+                                                            {show_load.then(|| {
+                                                                // Empty input = use the default
+                                                                let update_load = move |val: String, apply: fn(&mut crate::types::Exercise, Option<f64>)| {
+                                                                    let parsed = val.replace(',', ".").parse::<f64>().ok()
+                                                                        .filter(|v| *v > 0.0)
+                                                                        .map(|v| unit.to_kg(v));
+                                                                    let mut p = passes.get();
+                                                                    if let Some(pass) = p.get_mut(idx) {
+                                                                        if let Some(exercise) = pass.exercises.get_mut(ei) {
+                                                                            apply(exercise, parsed);
+                                                                        }
+                                                                    }
+                                                                    set_passes.set(p);
+                                                                };
+                                                                view! {
+                                                                    <div class="exercise-load-edit">
+                                                                        <span class="load-label">"Steg"</span>
+                                                                        <input type="number" step="0.25" class="load-input" value=ex_step placeholder=default_step
+                                                                            on:blur=move |e| update_load(event_target_value(&e), |ex, v| ex.weight_step = v)
+                                                                        />
+                                                                        <span class="load-label">"Min"</span>
+                                                                        <input type="number" step="0.5" class="load-input" value=ex_min placeholder="–"
+                                                                            on:blur=move |e| update_load(event_target_value(&e), |ex, v| ex.min_weight = v)
+                                                                        />
+                                                                        <span class="load-label">"Max"</span>
+                                                                        <input type="number" step="0.5" class="load-input" value=ex_max placeholder="–"
+                                                                            on:blur=move |e| update_load(event_target_value(&e), |ex, v| ex.max_weight = v)
+                                                                        />
+                                                                        <span class="load-label">{unit.label()}</span>
+                                                                    </div>
+                                                                }
+                                                            })}
+                                                            <div class="exercise-load-edit">
+                                                                <span class="load-label">"Vila"</span>
+                                                                <input type="number" step="15" class="load-input" value=ex_rest placeholder=default_rest
+                                                                    on:blur=move |e| {
+                                                                        // Empty input = default by rep range
+                                                                        let val = event_target_value(&e).parse::<u32>().ok();
+                                                                        let mut p = passes.get();
+                                                                        if let Some(pass) = p.get_mut(idx) {
+                                                                            if let Some(exercise) = pass.exercises.get_mut(ei) {
+                                                                                exercise.rest_secs = val;
+                                                                            }
+                                                                        }
+                                                                        set_passes.set(p);
+                                                                    }
+                                                                />
+                                                                <span class="load-label">"s"</span>
                                                             </div>
                                                         </div>
                                                         {if has_superset {
-                                                            let ex_name_unlink = ex_name_for_unlink.clone();
                                                             view! {
                                                                 <span class="superset-badge">{superset_info}</span>
-                                                                <button class="unlink-superset-btn" title="Bryt superset" on:click=move |_| {
+                                                                <button class="unlink-superset-btn" title="Ta ur gruppen" on:click=move |_| {
                                                                     let mut p = passes.get();
                                                                     if let Some(pass) = p.get_mut(idx) {
-                                                                        for ex in &mut pass.exercises {
-                                                                            if ex.name == ex_name_unlink || ex.superset_with.as_ref() == Some(&ex_name_unlink) {
-                                                                                ex.is_superset = false;
-                                                                                ex.superset_with = None;
-                                                                            }
+                                                                        if let Some(ex) = pass.exercises.get_mut(ei) {
+                                                                            ex.group = None;
+                                                                            ex.is_superset = false;
+                                                                            ex.superset_with = None;
                                                                         }
+                                                                        // A group left with one member dissolves
+                                                                        pass.normalize_groups();
                                                                     }
                                                                     set_passes.set(p);
                                                                 }>"✂"</button>
@@ -583,15 +586,10 @@ pub fn RoutineBuilder(
                                                         <button class="remove-exercise-btn" on:click=move |_| {
                                                             let mut p = passes.get();
                                                             if let Some(pass) = p.get_mut(idx) {
-                                                                if let Some(partner_name) = pass.exercises.get(ei).and_then(|e| e.superset_with.clone()) {
-                                                                    for other in &mut pass.exercises {
-                                                                        if other.name == partner_name {
-                                                                            other.is_superset = false;
-                                                                            other.superset_with = None;
-                                                                        }
-                                                                    }
+                                                                if ei < pass.exercises.len() {
+                                                                    pass.exercises.remove(ei);
                                                                 }
-                                                                pass.exercises.remove(ei);
+                                                                pass.normalize_groups();
                                                             }
                                                             set_passes.set(p);
                                                         }>"×"</button>
@@ -614,7 +612,10 @@ pub fn RoutineBuilder(
                                                 let is_timed = ex.duration_secs.is_some();
                                                 view! {
                                                     <div class="exercise-item finisher">
-                                                        <span class="exercise-name">{&ex.name}</span>
+                                                        <input type="text" class="exercise-name exercise-name-input" value=ex.name.clone()
+                                                            title="Byt namn – historiken följer med"
+                                                            on:blur=move |e| rename_exercise(idx, fi, true, event_target_value(&e))
+                                                        />
                                                         <div class="exercise-edit">
                                                             <input type="number" class="sets-input" value=fin_sets
                                                                 on:blur=move |e| {
@@ -637,10 +638,9 @@ pub fn RoutineBuilder(
                                                                         if let Some(exercise) = pass.finishers.get_mut(fi) {
                                                                             // Sync duration_secs if timed
                                                                             if exercise.duration_secs.is_some() {
-                                                                                let num = val.trim_end_matches(|c: char| !c.is_ascii_digit())
-                                                                                    .parse::<u32>().unwrap_or(30);
+                                                                                let num = crate::app::parse_duration(&val).unwrap_or(30);
                                                                                 exercise.duration_secs = Some(num);
-                                                                                exercise.reps_target = format!("{}s", num);
+                                                                                exercise.reps_target = crate::app::format_duration(num);
                                                                             } else {
                                                                                 exercise.reps_target = val;
                                                                             }
@@ -739,11 +739,13 @@ pub fn RoutineBuilder(
                                                                         };
                                                                         let new_ex = crate::types::Exercise {
                                                                             name: name_str.clone(),
+                                                                            id: None,
                                                                             sets: 2,
                                                                             reps_target: target_str.clone(),
                                                                             is_superset: false,
                                                                             superset_with: None,
                                                                             superset_name: None,
+                                                                            group: None,
                                                                             is_bodyweight: true,
                                                                             duration_secs: duration,
                                                                             primary_muscles: muscles_str.split(", ").map(|s| s.to_string()).collect(),
@@ -751,6 +753,14 @@ pub fn RoutineBuilder(
                                                                             image_url: None,
                                                                             equipment: Some("Kroppsvikt".to_string()),
                                                                             wger_id: None,
+                                                                            warmup: crate::types::WarmupScheme::Off,
+                                                                            weight_step: None,
+                                                                            min_weight: None,
+                                                                            max_weight: None,
+                                                                            rest_secs: None,
+                                                                            is_cardio: false,
+                                                                            block: None,
+                                                                            deload_weight: None,
                                                                         };
                                                                         pass.finishers.push(new_ex);
                                                                     }
@@ -814,14 +824,9 @@ pub fn RoutineBuilder(
                                                         let mut p = passes.get();
                                                         if let Some(pass) = p.get_mut(pi) {
                                                             let mut new_ex = crate::types::Exercise::from_wger(
-                                                                &ex_clone.name,
+                                                                &ex_clone,
                                                                 if fin { 2 } else { 3 },
                                                                 if fin { "10-15" } else { "8-12" },
-                                                                ex_clone.primary_muscles.clone(),
-                                                                ex_clone.secondary_muscles.clone(),
-                                                                ex_clone.image_url.clone(),
-                                                                ex_clone.equipment.clone(),
-                                                                ex_clone.base_id,
                                                             );
                                                             if fin {
                                                                 new_ex.is_bodyweight = true;
@@ -858,23 +863,26 @@ pub fn RoutineBuilder(
                             }
                         })}
 
-                        // Superset picker modal
+                        // Group picker modal: pair up two exercises or join an existing group
                         {move || linking_superset.get().map(|(pass_idx, exercise_idx)| {
                             let p = passes.get();
                             let pass = p.get(pass_idx);
                             let source_name = pass.and_then(|p| p.exercises.get(exercise_idx)).map(|e| e.name.clone()).unwrap_or_default();
                             let available: Vec<(usize, String)> = pass.map(|p| {
                                 p.exercises.iter().enumerate()
-                                    .filter(|(i, ex)| *i != exercise_idx && !ex.is_superset)
-                                    .map(|(i, ex)| (i, ex.name.clone()))
+                                    .filter(|(i, _)| *i != exercise_idx)
+                                    .map(|(i, ex)| match ex.group_key() {
+                                        Some(key) => (i, format!("{} ({} {})", ex.name, group_label(p.group_members(&key).len()), key)),
+                                        None => (i, ex.name.clone()),
+                                    })
                                     .collect()
                             }).unwrap_or_default();
 
                             view! {
                                 <div class="superset-picker-modal">
                                     <div class="superset-picker-dialog">
-                                        <h3>"Länka superset"</h3>
-                                        <p class="superset-source">{format!("Länka \"{}\" med:", source_name)}</p>
+                                        <h3>"Gruppera övningar"</h3>
+                                        <p class="superset-source">{format!("Kör \"{}\" i följd med:", source_name)}</p>
 
                                         {if available.is_empty() {
                                             view! { <p class="no-options">"Inga övningar att länka med"</p> }.into_view()
@@ -882,20 +890,22 @@ pub fn RoutineBuilder(
                                             view! {
                                                 <div class="superset-options">
                                                     {available.into_iter().map(|(other_idx, other_name)| {
-                                                        let name_for_closure = other_name.clone();
-                                                        let source_for_closure = source_name.clone();
                                                         view! {
                                                             <button class="superset-option" on:click=move |_| {
                                                                 let mut p = passes.get();
                                                                 if let Some(pass) = p.get_mut(pass_idx) {
-                                                                    if let Some(ex1) = pass.exercises.get_mut(exercise_idx) {
-                                                                        ex1.is_superset = true;
-                                                                        ex1.superset_with = Some(name_for_closure.clone());
-                                                                    }
-                                                                    if let Some(ex2) = pass.exercises.get_mut(other_idx) {
-                                                                        ex2.is_superset = true;
-                                                                        ex2.superset_with = Some(source_for_closure.clone());
+                                                                    // Join the other exercise's group, or start a new one with it
+                                                                    let key = pass.exercises.get(other_idx)
+                                                                        .and_then(|e| e.group_key())
+                                                                        .unwrap_or_else(|| "+".to_string());
+                                                                    for i in [exercise_idx, other_idx] {
+                                                                        if let Some(ex) = pass.exercises.get_mut(i) {
+                                                                            ex.group = Some(key.clone());
+                                                                            ex.is_superset = true;
+                                                                            ex.superset_with = None;
+                                                                        }
                                                                     }
+                                                                    pass.normalize_groups();
                                                                 }
                                                                 set_passes.set(p);
                                                                 set_linking_superset.set(None);
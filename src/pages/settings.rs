@@ -1,5 +1,7 @@
 use leptos::*;
-use crate::types::{AppView, AuthSession};
+use crate::types::{AppView, AthleteProfile, AuthSession, PlateSettings, Sex, WeightUnit};
+use crate::stats::E1rmFormula;
+use crate::app::{display_weight, format_weight_in};
 use crate::storage;
 use crate::supabase;
 
@@ -13,17 +15,16 @@ pub fn Settings(
     let (loading, set_loading) = create_signal(true);
 
     let db = storage::load_data();
+    let (unit, set_unit) = create_signal(storage::load_weight_unit());
     let initial_bw = db.get_bodyweight();
     let (bodyweight, set_bodyweight) = create_signal(initial_bw);
     let (editing_weight, set_editing_weight) = create_signal(false);
-    let (weight_input, set_weight_input) = create_signal(
-        initial_bw.map(|w| format!("{:.1}", w)).unwrap_or_default()
-    );
+    let (weight_input, set_weight_input) = create_signal(String::new());
 
     let save_bodyweight = move |_| {
-        if let Ok(w) = weight_input.get().parse::<f64>() {
+        if let Ok(entered) = weight_input.get().replace(',', ".").parse::<f64>() {
+            let w = unit.get().to_kg(entered);
             set_bodyweight.set(Some(w));
-            set_weight_input.set(format!("{:.1}", w));
 
             let mut local_db = storage::load_data();
             local_db.set_bodyweight(w);
@@ -39,6 +40,105 @@ pub fn Settings(
         set_editing_weight.set(false);
     };
 
+    let (plate_cfg, set_plate_cfg) = create_signal(db.plate_settings.clone());
+    let (plates_dirty, set_plates_dirty) = create_signal(false);
+    let (new_plate_input, set_new_plate_input) = create_signal(String::new());
+
+    let update_plates = move |f: &dyn Fn(&mut crate::types::PlateSettings)| {
+        set_plate_cfg.update(|cfg| f(cfg));
+        set_plates_dirty.set(true);
+    };
+
+    let add_plate_size = move |_| {
+        if let Ok(entered) = new_plate_input.get().replace(',', ".").parse::<f64>() {
+            if entered > 0.0 {
+                let w = unit.get().to_kg(entered);
+                update_plates(&|cfg| {
+                    if !cfg.plates.iter().any(|p| (p.weight - w).abs() < 0.001) {
+                        cfg.plates.push(crate::types::PlateStock { weight: w, pairs: 2 });
+                        cfg.plates.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+                    }
+                });
+            }
+        }
+        set_new_plate_input.set(String::new());
+    };
+
+    let save_plates = move || {
+        let cfg = plate_cfg.get();
+        let mut local_db = storage::load_data();
+        local_db.plate_settings = cfg.clone();
+        let _ = storage::save_data(&local_db);
+        supabase::save_plate_settings_to_cloud(&cfg);
+        set_plates_dirty.set(false);
+    };
+
+    let change_unit = move |new_unit: WeightUnit| {
+        let old_unit = unit.get();
+        if new_unit == old_unit {
+            return;
+        }
+        set_unit.set(new_unit);
+        storage::save_weight_unit(new_unit);
+        supabase::save_weight_unit_to_cloud(new_unit);
+
+        // Untouched standard plates follow the unit (metric gym ↔ lb gym)
+        if plate_cfg.get() == PlateSettings::standard(old_unit) {
+            set_plate_cfg.set(PlateSettings::standard(new_unit));
+            save_plates();
+        }
+    };
+
+    let (big_lifts, set_big_lifts) = create_signal(storage::load_big_lifts());
+    // Anything lifted with weight can count, plus the defaults for a fresh account
+    let lift_options = {
+        let mut ids: Vec<String> = crate::stats::BIG_FOUR.iter().map(|id| id.to_string()).collect();
+        ids.extend(big_lifts.get_untracked());
+        for record in db.sessions.iter().flat_map(|s| s.exercises.iter()) {
+            if record.rep_sets().any(|s| s.weight > 0.0) {
+                ids.push(record.exercise_id.clone());
+            }
+        }
+        let mut options: Vec<(String, String)> = ids.into_iter()
+            .map(|id| (db.exercise_name(&id), id))
+            .collect();
+        options.sort();
+        options.dedup_by(|a, b| a.1 == b.1);
+        options
+    };
+
+    let toggle_lift = move |id: String| {
+        let mut lifts = big_lifts.get();
+        if let Some(pos) = lifts.iter().position(|l| *l == id) {
+            // The total needs at least one lift
+            if lifts.len() == 1 {
+                return;
+            }
+            lifts.remove(pos);
+        } else {
+            lifts.push(id);
+        }
+        storage::save_big_lifts(&lifts);
+        supabase::save_big_lifts_to_cloud(&lifts);
+        set_big_lifts.set(lifts);
+    };
+
+    let (formula, set_formula) = create_signal(storage::load_e1rm_formula());
+    let change_formula = move |f: E1rmFormula| {
+        set_formula.set(f);
+        storage::save_e1rm_formula(f);
+        supabase::save_e1rm_formula_to_cloud(f);
+    };
+
+    let (profile, set_profile) = create_signal(storage::load_profile());
+    let update_profile = move |f: &dyn Fn(&mut AthleteProfile)| {
+        let mut p = profile.get();
+        f(&mut p);
+        storage::save_profile(&p);
+        supabase::save_profile_to_cloud(&p);
+        set_profile.set(p);
+    };
+
     let initial_name = storage::load_display_name().unwrap_or_default();
     let (display_name, set_display_name) = create_signal(initial_name.clone());
     let (editing_name, set_editing_name) = create_signal(false);
@@ -46,21 +146,18 @@ pub fn Settings(
 
     create_effect(move |_| {
         spawn_local(async move {
-            match supabase::fetch_display_name().await {
-                Ok(Some(cloud_name)) => {
-                    if !cloud_name.is_empty() {
-                        set_display_name.set(cloud_name.clone());
-                        set_name_input.set(cloud_name.clone());
-                        storage::save_display_name(&cloud_name);
-
-                        if let Some(mut session) = supabase::load_auth_session() {
-                            session.user.display_name = Some(cloud_name);
-                            supabase::save_auth_session(&session);
-                            set_auth.set(Some(session));
-                        }
+            if let Ok(Some(cloud_name)) = supabase::fetch_display_name().await {
+                if !cloud_name.is_empty() {
+                    set_display_name.set(cloud_name.clone());
+                    set_name_input.set(cloud_name.clone());
+                    storage::save_display_name(&cloud_name);
+
+                    if let Some(mut session) = supabase::load_auth_session() {
+                        session.user.display_name = Some(cloud_name);
+                        supabase::save_auth_session(&session);
+                        set_auth.set(Some(session));
                     }
                 }
-                _ => {}
             }
         });
     });
@@ -168,6 +265,54 @@ pub fn Settings(
                 </button>
             </section>
 
+            <section class="settings-section">
+                <h2>"Viktenhet"</h2>
+                <p class="settings-hint">"Vikter visas och matas in i vald enhet"</p>
+                <div class="unit-toggle">
+                    {[WeightUnit::Kg, WeightUnit::Lb].into_iter().map(|u| view! {
+                        <button
+                            class=move || if unit.get() == u { "unit-option selected" } else { "unit-option" }
+                            on:click=move |_| change_unit(u)
+                        >
+                            {u.label()}
+                        </button>
+                    }).collect_view()}
+                </div>
+            </section>
+
+            <section class="settings-section">
+                <h2>"Styrketotal"</h2>
+                <p class="settings-hint">"Lyft vars bästa E1RM räknas in i styrketotalen"</p>
+                <div class="unit-toggle option-picker">
+                    {lift_options.into_iter().map(|(name, id)| {
+                        let id_class = id.clone();
+                        view! {
+                            <button
+                                class=move || if big_lifts.with(|l| l.contains(&id_class)) { "unit-option selected" } else { "unit-option" }
+                                on:click=move |_| toggle_lift(id.clone())
+                            >
+                                {name}
+                            </button>
+                        }
+                    }).collect_view()}
+                </div>
+            </section>
+
+            <section class="settings-section">
+                <h2>"E1RM-formel"</h2>
+                <p class="settings-hint">"Hur 1RM uppskattas från dina set. RPE räknar in reps i reserv från dina RPE-betyg."</p>
+                <div class="unit-toggle option-picker">
+                    {E1rmFormula::ALL.into_iter().map(|f| view! {
+                        <button
+                            class=move || if formula.get() == f { "unit-option selected" } else { "unit-option" }
+                            on:click=move |_| change_formula(f)
+                        >
+                            {f.label()}
+                        </button>
+                    }).collect_view()}
+                </div>
+            </section>
+
             <section class="settings-section">
                 <h2>"Kroppsvikt"</h2>
                 <p class="settings-hint">"Används för att beräkna relativ styrka och kalorier"</p>
@@ -183,21 +328,21 @@ pub fn Settings(
                                         prop:value=weight_input
                                         on:input=move |ev| set_weight_input.set(event_target_value(&ev))
                                     />
-                                    <span class="bw-kg">"kg"</span>
+                                    <span class="bw-kg">{unit.get().label()}</span>
                                     <button class="bw-save" on:click=save_bodyweight>"✓"</button>
                                     <button class="bw-cancel" on:click=move |_| set_editing_weight.set(false)>"✕"</button>
                                 </div>
                             }.into_view()
                         } else {
                             let bw_display = bodyweight.get()
-                                .map(|w| format!("{:.1} kg", w))
+                                .map(|w| display_weight(w, unit.get()))
                                 .unwrap_or("Ej angiven".to_string());
                             view! {
                                 <div class="bw-display-row">
                                     <span class="bw-value">{bw_display}</span>
                                     <button class="bw-edit-btn" on:click=move |_| {
                                         let input_val = bodyweight.get()
-                                            .map(|w| format!("{:.1}", w))
+                                            .map(|w| format_weight_in(w, unit.get()))
                                             .unwrap_or_default();
                                         set_weight_input.set(input_val);
                                         set_editing_weight.set(true);
@@ -209,6 +354,101 @@ pub fn Settings(
                 </div>
             </section>
 
+            <section class="settings-section">
+                <h2>"Profil"</h2>
+                <p class="settings-hint">"Används för DOTS, Wilks och IPF GL. Åldersjusteras från 40 år."</p>
+                <div class="unit-toggle">
+                    {[Sex::Male, Sex::Female].into_iter().map(|sex| view! {
+                        <button
+                            class=move || if profile.get().sex == Some(sex) { "unit-option selected" } else { "unit-option" }
+                            on:click=move |_| update_profile(&|p| p.sex = Some(sex))
+                        >
+                            {sex.label()}
+                        </button>
+                    }).collect_view()}
+                </div>
+                <div class="plate-setting-row profile-row">
+                    <span class="plate-setting-name">"Födelseår"</span>
+                    <input
+                        type="number"
+                        min="1900"
+                        class="plate-setting-input"
+                        placeholder="ÅÅÅÅ"
+                        prop:value=move || profile.get().birth_year.map(|y| y.to_string()).unwrap_or_default()
+                        on:change=move |ev| {
+                            let year = event_target_value(&ev).parse::<i32>().ok().filter(|y| *y >= 1900);
+                            update_profile(&|p| p.birth_year = year);
+                        }
+                    />
+                </div>
+            </section>
+
+            <section class="settings-section">
+                <h2>"Skivstång & skivor"</h2>
+                <p class="settings-hint">"Används av skivkalkylatorn och uppvärmningen i passet"</p>
+                <div class="plate-setting">
+                    <div class="plate-setting-row">
+                        <span class="plate-setting-name">"Stång"</span>
+                        <input
+                            type="number"
+                            step="0.5"
+                            class="plate-setting-input"
+                            prop:value=move || format_weight_in(plate_cfg.get().bar_weight, unit.get())
+                            on:change=move |ev| {
+                                if let Ok(entered) = event_target_value(&ev).replace(',', ".").parse::<f64>() {
+                                    let w = unit.get().to_kg(entered);
+                                    update_plates(&|cfg| cfg.bar_weight = w.max(0.0));
+                                }
+                            }
+                        />
+                        <span class="bw-kg">{move || unit.get().label()}</span>
+                    </div>
+                    {move || plate_cfg.get().plates.into_iter().enumerate().map(|(i, stock)| {
+                        view! {
+                            <div class="plate-setting-row">
+                                <span class="plate-setting-name">{display_weight(stock.weight, unit.get())}</span>
+                                <input
+                                    type="number"
+                                    min="0"
+                                    class="plate-setting-input"
+                                    prop:value=stock.pairs.to_string()
+                                    on:change=move |ev| {
+                                        let pairs = event_target_value(&ev).parse::<u8>().unwrap_or(0);
+                                        update_plates(&|cfg| {
+                                            if let Some(p) = cfg.plates.get_mut(i) {
+                                                p.pairs = pairs;
+                                            }
+                                        });
+                                    }
+                                />
+                                <span class="bw-kg">"par"</span>
+                                <button class="plate-remove" on:click=move |_| {
+                                    update_plates(&|cfg| {
+                                        if i < cfg.plates.len() {
+                                            cfg.plates.remove(i);
+                                        }
+                                    });
+                                }>"✕"</button>
+                            </div>
+                        }
+                    }).collect_view()}
+                    <div class="plate-setting-row">
+                        <input
+                            type="number"
+                            step="0.25"
+                            class="plate-setting-input"
+                            placeholder=move || unit.get().label()
+                            prop:value=new_plate_input
+                            on:input=move |ev| set_new_plate_input.set(event_target_value(&ev))
+                        />
+                        <button class="bw-edit-btn" on:click=add_plate_size>"+ Skivstorlek"</button>
+                    </div>
+                    {move || plates_dirty.get().then(|| view! {
+                        <button class="plate-save" on:click=move |_| save_plates()>"Spara skivor"</button>
+                    })}
+                </div>
+            </section>
+
             <section class="settings-section">
                 <h2>"Visningsnamn"</h2>
                 <p class="settings-hint">"Visas på dashboarden istället för e-post"</p>
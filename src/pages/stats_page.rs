@@ -1,14 +1,25 @@
 use leptos::*;
 use crate::types::{AppView, AuthSession};
 use crate::storage;
-use crate::stats::{self, MuscleGroup, ProgressStatus, BIG_FOUR};
+use crate::stats::{self, LoadZone, MuscleFreshness, MuscleGroup, ProgressStatus};
 use crate::supabase;
-use crate::app::format_date;
+use crate::app::{display_weight, format_date, format_pace, format_pr_value, format_time, format_weight};
 
 #[component]
 pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSession>>) -> impl IntoView {
     let (sync_status, set_sync_status) = create_signal(storage::get_sync_status().to_string());
     let (data_version, set_data_version) = create_signal(0u32);
+    // Settings a sync may pull in; re-read whenever the data refreshes
+    let unit = create_memo(move |_| { data_version.track(); storage::load_weight_unit() });
+    let big_lifts = create_memo(move |_| { data_version.track(); storage::load_big_lifts() });
+    let formula = create_memo(move |_| { data_version.track(); storage::load_e1rm_formula() });
+    let profile = create_memo(move |_| { data_version.track(); storage::load_profile() });
+    // Which relative score the chart follows: 0 = DOTS, 1 = Wilks, 2 = IPF GL
+    let (score_tab, set_score_tab) = create_signal(0usize);
+    // Exercise shown in the rep-max table; None = first chosen lift with data
+    let (rm_exercise, set_rm_exercise) = create_signal(Option::<String>::None);
+    // Exercise whose PR timeline is shown; None = the one with the latest record
+    let (pr_exercise, set_pr_exercise) = create_signal(Option::<String>::None);
 
     create_effect(move |_| {
         let status = sync_status.get();
@@ -34,7 +45,7 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
     let load_summary = move || {
         let _ = data_version.get();
         let db = storage::load_data();
-        stats::get_stats_summary(&db, db.get_bodyweight().unwrap_or(80.0))
+        big_lifts.with(|lifts| stats::get_stats_summary(&db, db.get_bodyweight().unwrap_or(80.0), lifts, formula.get()))
     };
 
     view! {
@@ -54,14 +65,14 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                 // 1. STRENGTH TOTAL (hero)
                 <div class="stat-card hero-card">
                     <div class="hero-label">"STYRKETOTAL"</div>
-                    <div class="hero-value">{move || format!("{:.0}", load_summary().power_score)}<span class="hero-unit">" kg"</span></div>
+                    <div class="hero-value">{move || format!("{:.0}", unit.get().in_unit(load_summary().power_score))}<span class="hero-unit">{move || format!(" {}", unit.get().label())}</span></div>
                     <div class="hero-subtitle">
                         {move || {
                             let s = load_summary();
                             if s.bodyweight > 0.0 {
                                 format!("Styrkeratio: {:.2}x kroppsvikt", s.power_score / s.bodyweight)
                             } else {
-                                "Summa E1RM (Big 4)".to_string()
+                                format!("Summa E1RM ({} lyft)", big_lifts.with(Vec::len))
                             }
                         }}
                     </div>
@@ -69,12 +80,14 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                     <div class="big4-grid">
                         {move || {
                             let s = load_summary();
-                            BIG_FOUR.iter().map(|&name| {
-                                let e1rm = *s.e1rm_by_exercise.get(name).unwrap_or(&0.0);
+                            let db = storage::load_data();
+                            big_lifts.get().into_iter().map(|id| {
+                                let e1rm = *s.e1rm_by_exercise.get(&id).unwrap_or(&0.0);
+                                let name = db.exercise_name(&id);
                                 view! {
                                     <div class="big4-item">
                                         <span class="big4-name">{name}</span>
-                                        <span class="big4-value">{format!("{:.0}", e1rm)}</span>
+                                        <span class="big4-value">{format!("{:.0}", unit.get().in_unit(e1rm))}</span>
                                     </div>
                                 }
                             }).collect_view()
@@ -84,7 +97,7 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                     {move || {
                         let _ = data_version.get();
                         let db = storage::load_data();
-                        let ph = stats::get_power_score_history(&db);
+                        let ph = big_lifts.with(|lifts| stats::get_power_score_history(&db, lifts, formula.get()));
                         (!ph.is_empty()).then(|| {
                             let max = ph.iter().map(|(_, v)| *v).fold(0.0, f64::max);
                             let bars: Vec<f64> = ph.iter()
@@ -102,7 +115,252 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                     }}
                 </div>
 
-                // 2. WEEKLY VOLUME PER MUSCLE
+                // 2. RELATIVE STRENGTH (powerlift total against bodyweight)
+                {move || {
+                    let _ = data_version.get();
+                    let db = storage::load_data();
+                    if profile.with(|p| p.sex.is_none()) {
+                        return view! {
+                            <div class="stat-card">
+                                <div class="stat-card-title">"Relativ styrka"</div>
+                                <div class="empty-chart">"Ange kön under Inställningar → Profil för DOTS, Wilks och IPF GL"</div>
+                            </div>
+                        }.into_view();
+                    }
+                    let history = stats::relative_strength_history(&db, &profile.get(), formula.get());
+                    let Some(latest) = history.last().cloned() else { return ().into_view() };
+                    let pick = |tab: usize, r: &stats::RelativeScore| match tab {
+                        0 => r.dots,
+                        1 => r.wilks,
+                        _ => r.ipf_gl,
+                    };
+                    let wilks_label = if latest.age_factor > 1.0 { "Wilks (ålder)" } else { "Wilks" };
+                    let values = [("DOTS", latest.dots), (wilks_label, latest.wilks), ("IPF GL", latest.ipf_gl)];
+                    view! {
+                        <div class="stat-card">
+                            <div class="stat-card-title">"Relativ styrka"</div>
+                            <div class="stat-card-hint">
+                                {format!("Knäböj + bänk + mark: {} vid {} kroppsvikt", display_weight(latest.total, unit.get()), display_weight(latest.bodyweight, unit.get()))}
+                            </div>
+                            <div class="score-values">
+                                {values.into_iter().map(|(label, value)| view! {
+                                    <div class="score-value-item">
+                                        <span class="score-value">{format!("{:.1}", value)}</span>
+                                        <span class="score-value-label">{label}</span>
+                                    </div>
+                                }).collect_view()}
+                            </div>
+                            {(history.len() >= 2).then(|| view! {
+                                <div class="score-tabs">
+                                    {values.into_iter().enumerate().map(|(i, (label, _))| view! {
+                                        <button
+                                            class=move || if score_tab.get() == i { "score-tab selected" } else { "score-tab" }
+                                            on:click=move |_| set_score_tab.set(i)
+                                        >
+                                            {label}
+                                        </button>
+                                    }).collect_view()}
+                                </div>
+                                {move || {
+                                    let tab = score_tab.get();
+                                    let points: Vec<(i64, f64)> = history.iter().map(|r| (r.timestamp, pick(tab, r))).collect();
+                                    view! { <TrendChart points=points /> }
+                                }}
+                            })}
+                        </div>
+                    }.into_view()
+                }}
+
+                // 3. REP MAX TABLE (estimated from recent sessions)
+                {move || {
+                    let _ = data_version.get();
+                    let db = storage::load_data();
+                    let mut options: Vec<(String, String, f64)> = Vec::new();
+                    for record in db.sessions.iter().flat_map(|s| s.exercises.iter()) {
+                        if options.iter().any(|(id, _, _)| *id == record.exercise_id) {
+                            continue;
+                        }
+                        if let Some(e1rm) = stats::current_e1rm(&db, &record.exercise_id, formula.get()) {
+                            options.push((record.exercise_id.clone(), db.exercise_name(&record.exercise_id), e1rm));
+                        }
+                    }
+                    options.sort_by(|a, b| a.1.cmp(&b.1));
+                    (!options.is_empty()).then(|| {
+                        let selected = rm_exercise.get()
+                            .and_then(|id| options.iter().position(|(o, _, _)| *o == id))
+                            .or_else(|| big_lifts.with(|l| options.iter().position(|(o, _, _)| l.contains(o))))
+                            .unwrap_or(0);
+                        let count = options.len();
+                        let prev_id = options[(selected + count - 1) % count].0.clone();
+                        let next_id = options[(selected + 1) % count].0.clone();
+                        let (_, name, e1rm) = options[selected].clone();
+                        view! {
+                            <div class="stat-card">
+                                <div class="stat-card-title">"Repetitionsmax"</div>
+                                <div class="stat-card-hint">
+                                    {format!("Uppskattat med {} från dina senaste {} pass", formula.get().label(), stats::REP_MAX_SESSIONS)}
+                                </div>
+                                <div class="rm-nav">
+                                    <button class="rm-nav-btn" on:click=move |_| set_rm_exercise.set(Some(prev_id.clone()))>"‹"</button>
+                                    <span class="rm-nav-name">{name}</span>
+                                    <button class="rm-nav-btn" on:click=move |_| set_rm_exercise.set(Some(next_id.clone()))>"›"</button>
+                                </div>
+                                <div class="rm-table">
+                                    {stats::rep_max_table(e1rm, formula.get()).into_iter().map(|(reps, kg)| {
+                                        // Round to what can be loaded: half a kg or a whole lb
+                                        let step = if unit.get() == crate::types::WeightUnit::Lb { 1.0 } else { 0.5 };
+                                        let shown = (unit.get().in_unit(kg) / step).round() * step;
+                                        view! {
+                                            <div class="rm-cell">
+                                                <span class="rm-reps">{format!("{}RM", reps)}</span>
+                                                <span class="rm-weight">{format_weight(shown)}</span>
+                                            </div>
+                                        }
+                                    }).collect_view()}
+                                </div>
+                            </div>
+                        }
+                    })
+                }}
+
+                // 4. PERSONAL RECORDS (timeline per exercise, newest first)
+                {move || {
+                    let _ = data_version.get();
+                    let db = storage::load_data();
+                    let mut timelines: Vec<(String, Vec<stats::PersonalRecord>)> = Vec::new();
+                    for record in db.sessions.iter().flat_map(|s| s.exercises.iter()) {
+                        if timelines.iter().any(|(id, _)| *id == record.exercise_id) {
+                            continue;
+                        }
+                        let timeline = stats::pr_timeline(&db, &record.exercise_id, formula.get());
+                        if !timeline.is_empty() {
+                            timelines.push((record.exercise_id.clone(), timeline));
+                        }
+                    }
+                    timelines.sort_by_key(|(_, t)| std::cmp::Reverse(t.last().map(|pr| pr.timestamp)));
+                    (!timelines.is_empty()).then(|| {
+                        let selected = pr_exercise.get()
+                            .and_then(|id| timelines.iter().position(|(o, _)| *o == id))
+                            .unwrap_or(0);
+                        let count = timelines.len();
+                        let prev_id = timelines[(selected + count - 1) % count].0.clone();
+                        let next_id = timelines[(selected + 1) % count].0.clone();
+                        let (id, timeline) = timelines.swap_remove(selected);
+                        view! {
+                            <div class="stat-card">
+                                <div class="stat-card-title">"Personliga rekord"</div>
+                                <div class="stat-card-hint">{format!("{} rekord totalt", timeline.len())}</div>
+                                <div class="rm-nav">
+                                    <button class="rm-nav-btn" on:click=move |_| set_pr_exercise.set(Some(prev_id.clone()))>"‹"</button>
+                                    <span class="rm-nav-name">{db.exercise_name(&id)}</span>
+                                    <button class="rm-nav-btn" on:click=move |_| set_pr_exercise.set(Some(next_id.clone()))>"›"</button>
+                                </div>
+                                <div class="pr-list">
+                                    {timeline.iter().rev().take(15).map(|pr| view! {
+                                        <div class="pr-row">
+                                            <span class="pr-name">{format!("{} · {}", format_date(pr.timestamp), pr.kind.label())}</span>
+                                            <span class="pr-value">{format_pr_value(pr, unit.get())}</span>
+                                            <span class="pr-gain">{format!("+{:.1}%", (pr.value / pr.previous - 1.0) * 100.0)}</span>
+                                        </div>
+                                    }).collect_view()}
+                                </div>
+                            </div>
+                        }
+                    })
+                }}
+
+                // 5. TRAINING LOAD (acute vs chronic, readiness trend)
+                {move || {
+                    let _ = data_version.get();
+                    let db = storage::load_data();
+                    let history = stats::training_load_history(&db, 42);
+                    let ratios: Vec<(i64, f64)> = history.iter().filter_map(|p| p.ratio.map(|r| (p.timestamp, r))).collect();
+                    let latest = history.last().cloned()?;
+                    let ratio = latest.ratio?;
+                    let zone = LoadZone::from_ratio(ratio);
+                    // Rise over the last week, from the ratio seven days back
+                    let week_ago = ratios.iter().rev().nth(stats::ACUTE_DAYS as usize).map(|(_, r)| *r);
+                    let warning = match zone {
+                        LoadZone::Spike => Some("Kraftig belastningstopp – risk för överbelastning. Överväg en lättare vecka."),
+                        LoadZone::Elevated => Some("Belastningen ökar snabbt. Håll koll på återhämtningen."),
+                        _ if week_ago.is_some_and(|w| ratio - w >= 0.3) => Some("Belastningen har stigit snabbt den senaste veckan."),
+                        _ => None,
+                    };
+                    let zone_class = match zone {
+                        LoadZone::Low => "load-zone low",
+                        LoadZone::Optimal => "load-zone optimal",
+                        LoadZone::Elevated => "load-zone elevated",
+                        LoadZone::Spike => "load-zone spike",
+                    };
+                    Some(view! {
+                        <div class="stat-card">
+                            <div class="stat-card-title">"Belastning"</div>
+                            <div class="stat-card-hint">
+                                {format!("Akut ({} d) mot kronisk ({} d) belastning, 0.8–1.3 = optimalt", stats::ACUTE_DAYS, stats::CHRONIC_DAYS)}
+                            </div>
+                            <div class="load-values">
+                                <div class="load-value-item">
+                                    <span class="load-value">{format!("{:.2}", ratio)}</span>
+                                    <span class=zone_class>{zone.label()}</span>
+                                </div>
+                                <div class="load-value-item">
+                                    <span class="load-value">{format!("{:.1}", latest.acute)}</span>
+                                    <span class="load-value-label">"akut/dag"</span>
+                                </div>
+                                <div class="load-value-item">
+                                    <span class="load-value">{format!("{:.1}", latest.chronic)}</span>
+                                    <span class="load-value-label">"kronisk/dag"</span>
+                                </div>
+                            </div>
+                            {warning.map(|w| view! { <div class="load-warning">{w}</div> })}
+                            {(ratios.len() >= 2).then(|| view! {
+                                <TrendChart
+                                    points=ratios
+                                    guides=vec![LoadZone::LOW_BELOW, LoadZone::ELEVATED_FROM, LoadZone::SPIKE_FROM]
+                                />
+                            })}
+                        </div>
+                    })
+                }}
+
+                // 6. MUSCLE MAP (recovery and weekly volume per muscle)
+                {move || {
+                    let _ = data_version.get();
+                    let db = storage::load_data();
+                    let now = chrono::Utc::now().timestamp();
+                    let muscles = stats::muscle_recovery(&db, now);
+                    view! {
+                        <div class="stat-card">
+                            <div class="stat-card-title">"Återhämtning"</div>
+                            <div class="stat-card-hint">"Färg = återhämtning, styrka = set senaste 7 dagar"</div>
+                            <BodyMap muscles=muscles.clone() />
+                            <div class="recovery-list">
+                                {muscles.into_iter().map(|m| {
+                                    let status = match m.ready_at {
+                                        Some(ready) if m.recovery < 1.0 => format!("Redo om {} h", ((ready - now) as f64 / 3600.0).ceil() as i64),
+                                        _ => "Redo".to_string(),
+                                    };
+                                    let last = m.last_trained.map(|ts| match (now - ts) / 86400 {
+                                        0 => "idag".to_string(),
+                                        1 => "igår".to_string(),
+                                        d => format!("{} d sedan", d),
+                                    }).unwrap_or_else(|| "aldrig".to_string());
+                                    let row_class = if m.recovery < 1.0 { "recovery-row recovering" } else { "recovery-row" };
+                                    view! {
+                                        <div class=row_class>
+                                            <span class="recovery-dot" style=muscle_fill(&m)></span>
+                                            <span class="recovery-name">{m.muscle.name()}</span>
+                                            <span class="recovery-last">{last}</span>
+                                            <span class="recovery-status">{status}</span>
+                                        </div>
+                                    }
+                                }).collect_view()}
+                            </div>
+                        </div>
+                    }
+                }}
+
+                // 7. WEEKLY VOLUME PER MUSCLE
                 <div class="stat-card">
                     <div class="stat-card-title">"Veckovolym per muskel"</div>
                     <div class="stat-card-hint">"Set senaste 7 dagar (10-20 set/vecka = optimalt)"</div>
@@ -133,17 +391,93 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                     </div>
                 </div>
 
-                // 3. PROGRESSION (last session)
+                // 8. REST VS TARGET (only once rests have been timed against a target)
+                {move || {
+                    let rest = load_summary().rest_adherence;
+                    (rest.sets > 0).then(|| {
+                        let pct = |n: usize| (n as f64 / rest.sets as f64 * 100.0).round();
+                        let on_target = rest.sets - rest.over - rest.under;
+                        view! {
+                            <div class="stat-card">
+                                <div class="stat-card-title">"Vila mot mål"</div>
+                                <div class="stat-card-hint">{format!("{} set senaste 4 veckorna", rest.sets)}</div>
+                                <div class="rest-adherence">
+                                    <div class="rest-adherence-item">
+                                        <span class="rest-adherence-value">{format!("{:+.0} s", rest.avg_delta_secs)}</span>
+                                        <span class="rest-adherence-label">"snitt"</span>
+                                    </div>
+                                    <div class="rest-adherence-item">
+                                        <span class="rest-adherence-value">{format!("{:.0}%", pct(on_target))}</span>
+                                        <span class="rest-adherence-label">"i tid"</span>
+                                    </div>
+                                    <div class="rest-adherence-item">
+                                        <span class="rest-adherence-value">{format!("{:.0}%", pct(rest.over))}</span>
+                                        <span class="rest-adherence-label">"för lång"</span>
+                                    </div>
+                                    <div class="rest-adherence-item">
+                                        <span class="rest-adherence-value">{format!("{:.0}%", pct(rest.under))}</span>
+                                        <span class="rest-adherence-label">"för kort"</span>
+                                    </div>
+                                </div>
+                            </div>
+                        }
+                    })
+                }}
+
+                // 9. CARDIO (only once distance and time have been logged)
+                {move || {
+                    let cardio = load_summary().cardio;
+                    (cardio.weekly_distance_m > 0.0 || !cardio.trends.is_empty()).then(|| view! {
+                        <div class="stat-card">
+                            <div class="stat-card-title">"Kondition"</div>
+                            <div class="stat-card-hint">"Distans och tid senaste 7 dagarna"</div>
+                            <div class="cardio-totals">
+                                <div class="cardio-total">
+                                    <span class="cardio-total-value">{format!("{:.1} km", cardio.weekly_distance_m / 1000.0)}</span>
+                                    <span class="cardio-total-label">"distans"</span>
+                                </div>
+                                <div class="cardio-total">
+                                    <span class="cardio-total-value">{format_time(cardio.weekly_secs as i64)}</span>
+                                    <span class="cardio-total-label">"tid"</span>
+                                </div>
+                            </div>
+                            <div class="pace-list">
+                                {cardio.trends.into_iter().map(|trend| {
+                                    let latest = trend.paces.last().map(|(_, p)| *p).unwrap_or(0.0);
+                                    let first = trend.paces.first().map(|(_, p)| *p).unwrap_or(latest);
+                                    // Lower pace = faster
+                                    let delta = latest - first;
+                                    let (class, change) = if trend.paces.len() < 2 || delta.abs() < 1.0 {
+                                        ("pace-change", String::new())
+                                    } else if delta < 0.0 {
+                                        ("pace-change faster", format!("−{}", format_time(-delta as i64)))
+                                    } else {
+                                        ("pace-change slower", format!("+{}", format_time(delta as i64)))
+                                    };
+                                    view! {
+                                        <div class="pace-row">
+                                            <span class="pace-name">{trend.exercise}</span>
+                                            <span class="pace-value">{format_pace(latest)}</span>
+                                            <span class=class>{change}</span>
+                                        </div>
+                                    }
+                                }).collect_view()}
+                            </div>
+                        </div>
+                    })
+                }}
+
+                // 10. PROGRESSION (last session)
                 {move || {
                     let _ = data_version.get();
                     let db = storage::load_data();
                     let mut sessions = db.sessions.clone();
-                    sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-                    let statuses: Vec<(String, ProgressStatus)> = if let Some(session) = sessions.first() {
+                    sessions.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+                    let statuses: Vec<(String, String, ProgressStatus)> = if let Some(session) = sessions.first() {
                         session.exercises.iter()
                             .map(|e| {
-                                let status = stats::check_progressive_overload(&db, &e.name, session);
-                                (e.name.clone(), status)
+                                let status = stats::check_progressive_overload(&db, &e.exercise_id, session, formula.get());
+                                (e.exercise_id.clone(), e.name.clone(), status)
                             })
                             .collect()
                     } else {
@@ -153,7 +487,7 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                         <div class="stat-card">
                             <div class="stat-card-title">"Senaste passet"</div>
                             <div class="overload-grid">
-                                {statuses.iter().map(|(name, status)| {
+                                {statuses.iter().map(|(id, name, status)| {
                                     let class = match status {
                                         ProgressStatus::Improved => "improved",
                                         ProgressStatus::Maintained => "maintained",
@@ -161,11 +495,15 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                                         ProgressStatus::FirstTime => "first",
                                     };
                                     let name = name.clone();
+                                    let id = id.clone();
                                     view! {
-                                        <div class=format!("overload-item {}", class)>
+                                        <button
+                                            class=format!("overload-item {}", class)
+                                            on:click=move |_| set_view.set(AppView::ExerciseDetail(id.clone(), None))
+                                        >
                                             <span class="overload-icon"></span>
                                             <span class="overload-name">{name}</span>
-                                        </div>
+                                        </button>
                                     }
                                 }).collect_view()}
                             </div>
@@ -178,7 +516,7 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
                     })
                 }}
 
-                // 4. BODYWEIGHT (only if data exists)
+                // 11. BODYWEIGHT (only if data exists)
                 {move || {
                     let _ = data_version.get();
                     let db = storage::load_data();
@@ -201,12 +539,15 @@ pub fn Stats(set_view: WriteSignal<AppView>, set_auth: WriteSignal<Option<AuthSe
 pub fn WeightChart(history: Vec<crate::storage::BodyweightEntry>) -> impl IntoView {
     let now = (js_sys::Date::now() / 1000.0) as i64;
     let one_year_ago = now - (365 * 24 * 60 * 60);
+    let unit = crate::storage::load_weight_unit();
 
     let mut sorted = history.clone();
     sorted.sort_by_key(|h| h.timestamp);
 
+    // Chart in the user's unit
     let data: Vec<_> = sorted.into_iter()
         .filter(|h| h.timestamp >= one_year_ago)
+        .map(|h| crate::storage::BodyweightEntry { weight: unit.in_unit(h.weight), ..h })
         .collect();
 
     if data.len() < 2 {
@@ -275,3 +616,127 @@ pub fn WeightChart(history: Vec<crate::storage::BodyweightEntry>) -> impl IntoVi
         </div>
     }.into_view()
 }
+
+/// Body-map colour for a muscle: red when just trained through to green when recovered,
+/// more opaque the more sets it got this week
+fn muscle_fill(m: &MuscleFreshness) -> String {
+    if m.last_trained.is_none() {
+        return "fill: #2a2a2a; background: #2a2a2a".to_string();
+    }
+    let hue = m.recovery * 130.0;
+    let opacity = 0.35 + 0.65 * (m.weekly_sets / 20.0).min(1.0);
+    let color = format!("hsla({:.0}, 85%, 50%, {:.2})", hue, opacity);
+    format!("fill: {}; background: {}", color, color)
+}
+
+/// Front and back body outline with each muscle group coloured by `muscle_fill`
+#[component]
+fn BodyMap(muscles: Vec<MuscleFreshness>) -> impl IntoView {
+    let styles: std::collections::HashMap<MuscleGroup, String> = muscles.iter()
+        .map(|m| (m.muscle.clone(), muscle_fill(m)))
+        .collect();
+    let fill = |mg: MuscleGroup| styles.get(&mg).cloned().unwrap_or_default();
+
+    view! {
+        <div class="body-map">
+            <svg viewBox="0 0 200 185" class="body-map-svg">
+                // Front
+                <g>
+                    <circle cx="50" cy="14" r="9" class="body-base" />
+                    <rect x="46" y="22" width="8" height="7" class="body-base" />
+                    <ellipse cx="31" cy="38" rx="7" ry="6" style=fill(MuscleGroup::Shoulders) />
+                    <ellipse cx="69" cy="38" rx="7" ry="6" style=fill(MuscleGroup::Shoulders) />
+                    <rect x="36" y="32" width="13.5" height="16" rx="3" style=fill(MuscleGroup::Chest) />
+                    <rect x="50.5" y="32" width="13.5" height="16" rx="3" style=fill(MuscleGroup::Chest) />
+                    <ellipse cx="25" cy="56" rx="4.5" ry="11" style=fill(MuscleGroup::Biceps) />
+                    <ellipse cx="75" cy="56" rx="4.5" ry="11" style=fill(MuscleGroup::Biceps) />
+                    <ellipse cx="22" cy="80" rx="3.5" ry="11" class="body-base" />
+                    <ellipse cx="78" cy="80" rx="3.5" ry="11" class="body-base" />
+                    <rect x="39" y="50" width="22" height="32" rx="4" style=fill(MuscleGroup::Core) />
+                    <ellipse cx="42" cy="108" rx="8" ry="21" style=fill(MuscleGroup::Quads) />
+                    <ellipse cx="58" cy="108" rx="8" ry="21" style=fill(MuscleGroup::Quads) />
+                    <ellipse cx="42" cy="152" rx="5" ry="17" class="body-base" />
+                    <ellipse cx="58" cy="152" rx="5" ry="17" class="body-base" />
+                    <text x="50" y="182" class="body-map-label">"Fram"</text>
+                </g>
+                // Back
+                <g transform="translate(100 0)">
+                    <circle cx="50" cy="14" r="9" class="body-base" />
+                    <rect x="46" y="22" width="8" height="7" class="body-base" />
+                    <ellipse cx="31" cy="38" rx="7" ry="6" style=fill(MuscleGroup::Shoulders) />
+                    <ellipse cx="69" cy="38" rx="7" ry="6" style=fill(MuscleGroup::Shoulders) />
+                    <path d="M36,31 L64,31 L62,80 L38,80 Z" style=fill(MuscleGroup::Back) />
+                    <ellipse cx="25" cy="56" rx="4.5" ry="11" style=fill(MuscleGroup::Triceps) />
+                    <ellipse cx="75" cy="56" rx="4.5" ry="11" style=fill(MuscleGroup::Triceps) />
+                    <ellipse cx="22" cy="80" rx="3.5" ry="11" class="body-base" />
+                    <ellipse cx="78" cy="80" rx="3.5" ry="11" class="body-base" />
+                    <ellipse cx="43" cy="90" rx="8" ry="8" style=fill(MuscleGroup::Glutes) />
+                    <ellipse cx="57" cy="90" rx="8" ry="8" style=fill(MuscleGroup::Glutes) />
+                    <ellipse cx="42" cy="118" rx="7" ry="17" style=fill(MuscleGroup::Hamstrings) />
+                    <ellipse cx="58" cy="118" rx="7" ry="17" style=fill(MuscleGroup::Hamstrings) />
+                    <ellipse cx="42" cy="153" rx="5.5" ry="15" style=fill(MuscleGroup::Calves) />
+                    <ellipse cx="58" cy="153" rx="5.5" ry="15" style=fill(MuscleGroup::Calves) />
+                    <text x="50" y="182" class="body-map-label">"Bak"</text>
+                </g>
+            </svg>
+        </div>
+    }
+}
+
+/// Values over time as a line, scaled to their own range.
+/// `guides` are reference levels kept in view and marked instead of the min and max.
+#[component]
+pub fn TrendChart(points: Vec<(i64, f64)>, #[prop(optional)] guides: Vec<f64>) -> impl IntoView {
+    let values = || points.iter().map(|(_, v)| *v).chain(guides.iter().copied());
+    let min_val = values().fold(f64::INFINITY, f64::min);
+    let max_val = values().fold(f64::NEG_INFINITY, f64::max);
+    let pad_y = ((max_val - min_val) * 0.1).max(if guides.is_empty() { 1.0 } else { 0.05 });
+    let (min_v, max_v) = (min_val - pad_y, max_val + pad_y);
+    let marks: Vec<(f64, String)> = if guides.is_empty() {
+        vec![(min_val, format!("{:.0}", min_val)), (max_val, format!("{:.0}", max_val))]
+    } else {
+        guides.iter().map(|g| (*g, format!("{:.1}", g))).collect()
+    };
+
+    let padding = 15.0;
+    let width = 100.0;
+    let height = 60.0;
+
+    let first_ts = points.first().map(|(t, _)| *t).unwrap_or(0);
+    let last_ts = points.last().map(|(t, _)| *t).unwrap_or(0);
+    let time_range = (last_ts - first_ts).max(1) as f64;
+
+    let get_x = move |ts: i64| padding + ((ts - first_ts) as f64 / time_range * (width - 2.0 * padding));
+    let get_y = move |v: f64| height - padding - ((v - min_v) / (max_v - min_v) * (height - 2.0 * padding));
+
+    let line: String = points.iter()
+        .map(|(t, v)| format!("{},{}", get_x(*t), get_y(*v)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    view! {
+        <div class="weight-chart-container">
+            <svg viewBox=format!("0 0 {} {}", width, height) class="weight-chart-svg">
+                {marks.into_iter().map(|(v, label)| view! {
+                    <line x1=padding y1={get_y(v)} x2={width-padding} y2={get_y(v)} stroke="#222" stroke-width="0.3" stroke-dasharray="1,1" />
+                    <text x={padding - 2.0} y={get_y(v) + 1.0} font-size="3" fill="#666" text-anchor="end" font-family="var(--font)">{label}</text>
+                }).collect_view()}
+
+                <polyline points=line class="weight-line" />
+                {points.last().map(|(t, v)| view! {
+                    <circle cx={get_x(*t)} cy={get_y(*v)} r="1.0" class="weight-point" />
+                })}
+            </svg>
+            <div class="weight-stats-row">
+                <div class="weight-stat">
+                    <span class="weight-stat-label">{format_date(first_ts)}</span>
+                    <span class="weight-stat-val">{format!("{:.1}", points.first().map(|(_, v)| *v).unwrap_or(0.0))}</span>
+                </div>
+                <div class="weight-stat">
+                    <span class="weight-stat-label">"Nu"</span>
+                    <span class="weight-stat-val highlight">{format!("{:.1}", points.last().map(|(_, v)| *v).unwrap_or(0.0))}</span>
+                </div>
+            </div>
+        </div>
+    }
+}
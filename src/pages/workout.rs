@@ -1,10 +1,27 @@
 use leptos::*;
 use crate::types::{
-    AppView, WorkoutData, SetRecord, ExerciseRecord, ExerciseWorkoutState,
+    AppView, WorkoutData, SetRecord, SetKind, CardioStats, Exercise, ExerciseRecord, ExerciseWorkoutState,
+    BlockFormat, group_label,
 };
 use crate::storage;
 use crate::supabase;
-use crate::app::{format_time, format_weight, parse_target_range, parse_target_reps};
+use crate::app::{
+    display_weight, format_duration, format_pace, format_pr_value, format_time, format_weight,
+    format_weight_in, parse_target_range, parse_target_reps,
+};
+use crate::stats::{PersonalRecord, RPE_SCALE, rpe_to_rir};
+use crate::plates;
+use crate::wger::{search_wger_exercises, WgerExercise};
+
+/// Rest when moving on to the next member of a group within the same round
+const GROUP_CHANGEOVER_SECS: u32 = 15;
+
+/// What the exercise picker modal is choosing for
+#[derive(Clone, Copy, PartialEq)]
+enum ExercisePicker {
+    Swap(usize),
+    Add,
+}
 
 #[component]
 pub fn Workout(routine: String, set_view: WriteSignal<AppView>) -> impl IntoView {
@@ -14,13 +31,29 @@ pub fn Workout(routine: String, set_view: WriteSignal<AppView>) -> impl IntoView
         if paused.routine_name == pass_name {
             let data = storage::get_workout(&pass_name);
             if let Some(mut d) = data {
-                d.exercises = paused.exercises;
+                d.exercises = paused.exercises.clone();
+                // A paused clock picks up where it stopped; coming back from the
+                // exercise history also counts the time spent there
+                let (start_elapsed, last_set_time) = match paused.left_at.filter(|_| paused.clock_running) {
+                    Some(left_at) => {
+                        let away = (js_sys::Date::now() as i64 / 1000 - left_at).max(0);
+                        // Only this return counts; a later reload resumes like a pause
+                        let _ = storage::save_paused_workout(&crate::types::PausedWorkout {
+                            left_at: None,
+                            clock_running: false,
+                            ..paused.clone()
+                        });
+                        (paused.elapsed_secs + away, paused.last_set_time)
+                    }
+                    None => (paused.elapsed_secs, 0),
+                };
                 return view! {
                     <WorkoutActive
                         data=d
                         set_view=set_view
                         resumed_from=paused.current_exercise_idx
-                        start_elapsed=paused.elapsed_secs
+                        start_elapsed=start_elapsed
+                        resumed_last_set_time=last_set_time
                     />
                 }.into_view();
             }
@@ -45,21 +78,22 @@ pub fn WorkoutActive(
     set_view: WriteSignal<AppView>,
     #[prop(default = 0)] resumed_from: usize,
     #[prop(default = 0)] start_elapsed: i64,
+    #[prop(default = 0)] resumed_last_set_time: i64,
 ) -> impl IntoView {
     let routine = data.routine.clone();
     let routine_name = routine.name.clone();
     let routine_name_save = routine_name.clone();
-    let routine_name_pause = routine_name.clone();
 
     let db = storage::load_data();
     let bodyweight = db.get_bodyweight().unwrap_or(80.0);
+    let plate_settings = store_value(db.plate_settings.clone());
+    let unit = storage::load_weight_unit();
 
-    let total_exercises = data.exercises.len();
     let (exercises, set_exercises) = create_signal(data.exercises);
     let (current_idx, set_current_idx) = create_signal(resumed_from);
     let (start_time, _) = create_signal(js_sys::Date::now() as i64 / 1000 - start_elapsed);
     let (elapsed, set_elapsed) = create_signal(start_elapsed);
-    let (last_set_time, set_last_set_time) = create_signal(0i64);
+    let (last_set_time, set_last_set_time) = create_signal(resumed_last_set_time);
     let (rest_elapsed, set_rest_elapsed) = create_signal(0i64);
     let (is_resting, set_is_resting) = create_signal(false);
     let (is_finished, set_is_finished) = create_signal(false);
@@ -67,48 +101,172 @@ pub fn WorkoutActive(
     let (show_cancel_confirm, set_show_cancel_confirm) = create_signal(false);
     let (is_saving, set_is_saving) = create_signal(false);
     let (show_sync_warning, set_show_sync_warning) = create_signal(false);
+    // Records the saved session set; with any, the finish screen waits for the user
+    let (new_prs, set_new_prs) = create_signal(Vec::<PersonalRecord>::new());
+    let (is_saved, set_is_saved) = create_signal(false);
+    // Exercise whose latest set is waiting for an RPE rating on the rest screen
+    let (rpe_pending, set_rpe_pending) = create_signal(Option::<usize>::None);
+    let (next_set_kind, set_next_set_kind) = create_signal(SetKind::Working);
+    let (warmup_dismissed, set_warmup_dismissed) = create_signal(Vec::<usize>::new());
+    let (show_plates, set_show_plates) = create_signal(false);
+    let (picker, set_picker) = create_signal(Option::<ExercisePicker>::None);
+    let (picker_query, set_picker_query) = create_signal(String::new());
+    let (picker_results, set_picker_results) = create_signal(Vec::<WgerExercise>::new());
+    let (picker_searching, set_picker_searching) = create_signal(false);
+    let picker_debounce = store_value(None::<gloo_timers::callback::Timeout>);
+    // Cardio interval inputs, kept as typed until the interval is saved
+    let (cardio_km, set_cardio_km) = create_signal(String::new());
+    let (cardio_min, set_cardio_min) = create_signal(String::new());
+    let (cardio_sec, set_cardio_sec) = create_signal(String::new());
+    let (cardio_kcal, set_cardio_kcal) = create_signal(String::new());
+    let (cardio_hr, set_cardio_hr) = create_signal(String::new());
+    let (cardio_clock, set_cardio_clock) = create_signal(Option::<f64>::None);  // stopwatch start, ms
+    let clear_cardio = move || {
+        for set in [set_cardio_km, set_cardio_min, set_cardio_sec, set_cardio_kcal, set_cardio_hr] {
+            set.set(String::new());
+        }
+        set_cardio_clock.set(None);
+    };
+    // Rest countdown toward the upcoming exercise's target, nudged ±15 s on the rest screen
+    let (rest_adjust, set_rest_adjust) = create_signal(0i64);
+    let (rest_alerted, set_rest_alerted) = create_signal(false);
+    let audio_ctx = store_value(None::<web_sys::AudioContext>);
+    // Moving on to the next member of a group within the same round only needs a changeover
+    let (in_round, set_in_round) = create_signal(false);
+    let rest_target = move || {
+        let base = if in_round.get() {
+            GROUP_CHANGEOVER_SECS
+        } else {
+            exercises.with(|exs| exs.get(current_idx.get()).map(|e| e.exercise.rest_target_secs()).unwrap_or(120))
+        };
+        (base as i64 + rest_adjust.get()).max(0)
+    };
+    on_cleanup(move || audio_ctx.with_value(|c| {
+        if let Some(c) = c {
+            let _ = c.close();
+        }
+    }));
+
+    // Set kind is chosen per exercise - start every exercise on working sets
+    create_effect(move |_| {
+        let _ = current_idx.get();
+        set_next_set_kind.set(SetKind::Working);
+    });
 
     let (timer_running, set_timer_running) = create_signal(false);
     let (timer_selected_duration, set_timer_selected_duration) = create_signal(30u32);
     let (timer_remaining, set_timer_remaining) = create_signal(0i32);
+    let (timer_ends_at, set_timer_ends_at) = create_signal(0.0f64);  // ms timestamp
+    let (timer_started_at, set_timer_started_at) = create_signal(0.0f64);  // ms timestamp
     let (show_timer_flash, set_show_timer_flash) = create_signal(false);
 
+    // Timed exercises start on last time's duration, else the target
+    create_effect(move |_| {
+        let idx = current_idx.get();
+        clear_cardio();
+        let ex = exercises.with_untracked(|exs| exs.get(idx).map(|e| e.exercise.clone()));
+        if let Some(ex) = ex {
+            if let Some(target) = ex.duration_secs {
+                let last = storage::load_data().last_duration(&ex.name);
+                set_timer_selected_duration.set(last.unwrap_or(target));
+            }
+        }
+    });
+
     let jump_to_exercise = move |idx: usize| {
         set_current_idx.set(idx);
+        set_in_round.set(false);
         set_is_resting.set(false);
         set_show_overview.set(false);
     };
 
     let (timer_just_completed, set_timer_just_completed) = create_signal(false);
 
-    create_effect(move |_| {
-        let handle = gloo_timers::callback::Interval::new(1000, move || {
-            let now = js_sys::Date::now() as i64 / 1000;
-            set_elapsed.set(now - start_time.get());
-            if is_resting.get() && last_set_time.get() > 0 {
-                set_rest_elapsed.set(now - last_set_time.get());
+    // All timers derive from wall-clock timestamps, so a throttled or
+    // backgrounded tab catches up on the next tick instead of losing time
+    let tick = move || {
+        let now_ms = js_sys::Date::now();
+        let now = now_ms as i64 / 1000;
+        set_elapsed.set(now - start_time.get());
+        if is_resting.get() && last_set_time.get() > 0 {
+            let rested = now - last_set_time.get();
+            set_rest_elapsed.set(rested);
+            if !rest_alerted.get() && rested >= rest_target() {
+                set_rest_alerted.set(true);
+                rest_done_alert(audio_ctx);
             }
-            if timer_running.get() {
-                let remaining = timer_remaining.get() - 1;
-                if remaining <= 0 {
-                    set_timer_remaining.set(0);
-                    set_timer_running.set(false);
-                    set_show_timer_flash.set(true);
-                    gloo_timers::callback::Timeout::new(800, move || {
-                        set_show_timer_flash.set(false);
-                        set_timer_just_completed.set(true);
-                    }).forget();
-                } else {
-                    set_timer_remaining.set(remaining);
+        }
+        if timer_running.get() {
+            let remaining = ((timer_ends_at.get() - now_ms) / 1000.0).ceil() as i32;
+            if remaining <= 0 {
+                set_timer_remaining.set(0);
+                set_timer_running.set(false);
+                set_show_timer_flash.set(true);
+                gloo_timers::callback::Timeout::new(800, move || {
+                    set_show_timer_flash.set(false);
+                    set_timer_just_completed.set(true);
+                }).forget();
+            } else {
+                set_timer_remaining.set(remaining);
+            }
+        }
+    };
+
+    create_effect(move |_| {
+        let handle = gloo_timers::callback::Interval::new(1000, tick);
+        on_cleanup(move || drop(handle));
+    });
+
+    // Keep the screen on during the workout. Browsers drop the lock when the
+    // tab is hidden, so it's re-acquired when the page becomes visible again.
+    let wake_lock = store_value(None::<wasm_bindgen::JsValue>);
+    let acquire_wake_lock = move || {
+        spawn_local(async move {
+            if let Some(sentinel) = request_wake_lock().await {
+                let stored = wake_lock.try_update_value(|w| {
+                    if let Some(old) = w.replace(sentinel.clone()) {
+                        release_wake_lock(&old);
+                    }
+                });
+                // Workout was left while the request was pending
+                if stored.is_none() {
+                    release_wake_lock(&sentinel);
                 }
             }
         });
-        on_cleanup(move || drop(handle));
+    };
+    let drop_wake_lock = move || {
+        wake_lock.update_value(|w| {
+            if let Some(sentinel) = w.take() {
+                release_wake_lock(&sentinel);
+            }
+        });
+    };
+
+    create_effect(move |_| {
+        if is_finished.get() {
+            drop_wake_lock();
+        } else {
+            acquire_wake_lock();
+        }
+    });
+
+    let visibility_handle = window_event_listener_untyped("visibilitychange", move |_| {
+        if document().visibility_state() == web_sys::VisibilityState::Visible {
+            tick();
+            if !is_finished.get_untracked() {
+                acquire_wake_lock();
+            }
+        }
+    });
+    on_cleanup(move || {
+        visibility_handle.remove();
+        drop_wake_lock();
     });
 
     let current_exercise = move || exercises.get().get(current_idx.get()).cloned();
     let current_set_num = move || {
-        current_exercise().map(|e| e.sets_completed.len() + 1).unwrap_or(1)
+        current_exercise().map(|e| e.working_sets_done() + 1).unwrap_or(1)
     };
     let total_sets = move || {
         current_exercise().map(|e| e.exercise.sets as usize).unwrap_or(0)
@@ -123,25 +281,15 @@ pub fn WorkoutActive(
         current_exercise().map(|e| e.exercise.reps_target.clone()).unwrap_or_default()
     };
 
-    let find_partner_idx = move |exs: &[ExerciseWorkoutState], current_idx: usize| -> Option<usize> {
-        let current = &exs[current_idx];
-        if !current.exercise.is_superset {
-            return None;
-        }
-        let partner_name = current.exercise.superset_with.as_ref()?;
-        exs.iter().position(|e| &e.exercise.name == partner_name)
-    };
-
-    let complete_set = move |reps: u8| {
+    // `duration_secs` is Some for timed sets, which log 0 reps; cardio intervals add `cardio`
+    let complete_set = move |reps: u8, duration_secs: Option<u32>, cardio: Option<CardioStats>| {
         let now = js_sys::Date::now() as i64 / 1000;
         let rest = if last_set_time.get() > 0 { Some(now - last_set_time.get()) } else { None };
         let idx = current_idx.get();
+        let kind = next_set_kind.get();
+        let rest_target_secs = rest.map(|_| rest_target() as u32);
 
-        let exs = exercises.get();
-        let sets_done = exs[idx].sets_completed.len();
-        let sets_target = exs[idx].exercise.sets as usize;
-        let is_superset = exs[idx].exercise.is_superset;
-        let _is_last_exercise = idx + 1 >= exs.len();
+        let rateable = duration_secs.is_none() && cardio.is_none();
 
         set_exercises.update(|exs| {
             if let Some(ex) = exs.get_mut(idx) {
@@ -150,57 +298,113 @@ pub fn WorkoutActive(
                     reps,
                     timestamp: now,
                     rest_before_secs: rest,
+                    rpe: None,
+                    kind,
+                    rest_target_secs,
+                    duration_secs,
+                    cardio: cardio.clone(),
                 });
             }
         });
 
+        set_rpe_pending.set((rateable && kind.counts_as_work()).then_some(idx));
         set_last_set_time.set(now);
         set_rest_elapsed.set(0);
+        set_rest_adjust.set(0);
+        set_rest_alerted.set(false);
+        unlock_audio(audio_ctx);
+
+        // Warm-ups don't advance the exercise or the group rotation
+        if !kind.counts_as_work() {
+            set_in_round.set(false);
+            set_is_resting.set(true);
+            return;
+        }
 
-        let just_finished_exercise = sets_done + 1 >= sets_target;
-
-        if just_finished_exercise {
-            if is_superset {
-                if let Some(partner_idx) = find_partner_idx(&exs, idx) {
-                    let partner = &exs[partner_idx];
-                    let partner_done = partner.sets_completed.len() >= partner.exercise.sets as usize;
-                    if !partner_done {
-                        set_current_idx.set(partner_idx);
-                        set_is_resting.set(true);
+        // Go round the group, then on to the first unfinished exercise after it
+        let exs = exercises.get_untracked();
+        match next_in_group(&exs, idx) {
+            Some(next) => {
+                set_in_round.set(next > idx);
+                set_current_idx.set(next);
+            }
+            None => {
+                set_in_round.set(false);
+                match (idx + 1..exs.len()).find(|&i| !exs[i].is_done()) {
+                    Some(next) => set_current_idx.set(next),
+                    None => {
+                        set_is_finished.set(true);
                         return;
                     }
                 }
             }
-            let mut next_idx = idx + 1;
-            while next_idx < exs.len() {
-                let next_ex = &exs[next_idx];
-                let next_done = next_ex.sets_completed.len() >= next_ex.exercise.sets as usize;
-                if !next_done {
-                    break;
-                }
-                next_idx += 1;
-            }
-            if next_idx < exs.len() {
-                set_current_idx.set(next_idx);
-            } else {
-                set_is_finished.set(true);
-                return;
-            }
-        } else if is_superset {
-            if let Some(partner_idx) = find_partner_idx(&exs, idx) {
-                let partner = &exs[partner_idx];
-                let partner_done = partner.sets_completed.len() >= partner.exercise.sets as usize;
-                if !partner_done {
-                    set_current_idx.set(partner_idx);
-                }
-            }
         }
         set_is_resting.set(true);
     };
 
+    // Warm-ups from the proposed ramp: logged without leaving the exercise screen
+    let log_warmup_set = move |weight: f64, reps: u8| {
+        let now = js_sys::Date::now() as i64 / 1000;
+        let rest = if last_set_time.get() > 0 { Some(now - last_set_time.get()) } else { None };
+        let idx = current_idx.get();
+        set_exercises.update(|exs| {
+            if let Some(ex) = exs.get_mut(idx) {
+                ex.sets_completed.push(SetRecord {
+                    weight,
+                    reps,
+                    timestamp: now,
+                    rest_before_secs: rest,
+                    rpe: None,
+                    kind: SetKind::Warmup,
+                    rest_target_secs: None,
+                    duration_secs: None,
+                    cardio: None,
+                });
+            }
+        });
+        set_last_set_time.set(now);
+    };
+
+    let cardio_secs = move || {
+        let mins = cardio_min.get().parse::<u32>().unwrap_or(0);
+        let secs = cardio_sec.get().parse::<u32>().unwrap_or(0);
+        mins * 60 + secs
+    };
+    let cardio_distance_m = move || {
+        cardio_km.get().replace(',', ".").parse::<f64>().ok().filter(|km| *km > 0.0).map(|km| km * 1000.0)
+    };
+
+    let save_cardio_interval = move || {
+        let secs = cardio_secs();
+        let distance_m = cardio_distance_m();
+        if secs == 0 && distance_m.is_none() {
+            return;
+        }
+        let stats = CardioStats {
+            distance_m,
+            calories: cardio_kcal.get().parse().ok(),
+            avg_hr: cardio_hr.get().parse().ok(),
+        };
+        clear_cardio();
+        complete_set(0, (secs > 0).then_some(secs), Some(stats));
+    };
+
+    // Stopwatch for the interval; stopping fills in the time fields
+    let toggle_cardio_clock = move || match cardio_clock.get_untracked() {
+        Some(start) => {
+            let secs = ((js_sys::Date::now() - start) / 1000.0).round() as u32;
+            set_cardio_min.set((secs / 60).to_string());
+            set_cardio_sec.set((secs % 60).to_string());
+            set_cardio_clock.set(None);
+        }
+        None => set_cardio_clock.set(Some(js_sys::Date::now())),
+    };
+
+    // Logs the time actually held: the full countdown, or less when stopped early
     let complete_timed_set = move || {
-        let duration = timer_selected_duration.get();
-        complete_set(duration as u8);
+        let held = ((timer_ends_at.get().min(js_sys::Date::now()) - timer_started_at.get()) / 1000.0).round();
+        set_timer_running.set(false);
+        complete_set(0, Some(held.max(1.0) as u32), None);
     };
 
     create_effect(move |_| {
@@ -212,14 +416,26 @@ pub fn WorkoutActive(
 
     let start_timer = move |_| {
         let duration = timer_selected_duration.get();
+        let now = js_sys::Date::now();
+        set_timer_started_at.set(now);
+        set_timer_ends_at.set(now + duration as f64 * 1000.0);
         set_timer_remaining.set(duration as i32);
         set_timer_running.set(true);
     };
 
     let continue_workout = move |_| {
+        set_rpe_pending.set(None);
         set_is_resting.set(false);
     };
 
+    let rate_last_set = move |idx: usize, rpe: f64| {
+        set_exercises.update(|exs| {
+            if let Some(set) = exs.get_mut(idx).and_then(|e| e.sets_completed.last_mut()) {
+                set.rpe = Some(rpe);
+            }
+        });
+    };
+
     let skip_exercise = move |_| {
         let idx = current_idx.get();
         let exs = exercises.get();
@@ -233,17 +449,419 @@ pub fn WorkoutActive(
         }
     };
 
-    let adjust_weight = move |delta: f64| {
+    // +1 / -1 steps of the exercise's own weight increment
+    let adjust_weight = move |direction: f64| {
+        let idx = current_idx.get();
+        set_exercises.update(|exs| {
+            if let Some(ex) = exs.get_mut(idx) {
+                let step = ex.exercise.weight_step(unit);
+                ex.current_weight = ex.exercise.clamp_weight((ex.current_weight + direction * step).max(0.0));
+            }
+        });
+    };
+
+    let set_weight = move |weight: f64| {
         let idx = current_idx.get();
         set_exercises.update(|exs| {
             if let Some(ex) = exs.get_mut(idx) {
-                ex.current_weight = (ex.current_weight + delta).max(0.0);
+                ex.current_weight = weight;
             }
         });
     };
 
     let (routine_name_sig, _) = create_signal(routine_name_save);
 
+    let snapshot = move || crate::types::PausedWorkout {
+        routine_name: routine_name_sig.get(),
+        exercises: exercises.get(),
+        current_exercise_idx: current_idx.get(),
+        start_timestamp: start_time.get(),
+        elapsed_secs: elapsed.get(),
+        last_set_time: last_set_time.get(),
+        left_at: None,
+        clock_running: false,
+    };
+
+    // Edited sets change what the rest clock counts from, and a resumed
+    // workout's snapshot must not bring the old sets back after a reload
+    let sync_after_set_edit = move || {
+        let latest = exercises.with(|exs| {
+            exs.iter().flat_map(|e| &e.sets_completed).map(|s| s.timestamp).max().unwrap_or(0)
+        });
+        set_last_set_time.set(latest);
+        if storage::load_paused_workout().is_some_and(|p| p.routine_name == routine_name_sig.get()) {
+            let _ = storage::save_paused_workout(&snapshot());
+        }
+    };
+
+    // Leave for the exercise's history page. Its back button resumes from the snapshot
+    // with the time away counted; any other way back resumes like a pause.
+    let open_exercise_history = move || {
+        let Some(ex) = current_exercise() else { return };
+        let _ = storage::save_paused_workout(&crate::types::PausedWorkout {
+            left_at: Some(js_sys::Date::now() as i64 / 1000),
+            ..snapshot()
+        });
+        let id = storage::load_data().id_of(&ex.exercise);
+        set_view.set(AppView::ExerciseDetail(id, Some(routine_name_sig.get_untracked())));
+    };
+
+    // Undo the most recently logged set, wherever the group rotation has moved on to
+    let undo_last_set = move || {
+        let latest = exercises.with(|exs| {
+            exs.iter().enumerate()
+                .filter_map(|(i, e)| e.sets_completed.last().map(|s| (i, s.timestamp)))
+                .max_by_key(|(_, ts)| *ts)
+                .map(|(i, _)| i)
+        });
+        let idx = match latest {
+            Some(i) => i,
+            None => return,
+        };
+        let mut undone = None;
+        set_exercises.update(|exs| {
+            if let Some(ex) = exs.get_mut(idx) {
+                undone = ex.sets_completed.pop();
+                if let Some(set) = &undone {
+                    ex.current_weight = set.weight;
+                }
+            }
+        });
+        set_rpe_pending.set(None);
+        set_in_round.set(false);
+        set_is_resting.set(false);
+        set_is_finished.set(false);
+        set_current_idx.set(idx);
+        if let Some(set) = undone {
+            set_next_set_kind.set(set.kind);
+        }
+        sync_after_set_edit();
+    };
+
+    let edit_set = move |ex_idx: usize, set_idx: usize, edit: &dyn Fn(&mut SetRecord)| {
+        set_exercises.update(|exs| {
+            if let Some(set) = exs.get_mut(ex_idx).and_then(|e| e.sets_completed.get_mut(set_idx)) {
+                edit(set);
+            }
+        });
+        sync_after_set_edit();
+    };
+
+    let delete_set = move |ex_idx: usize, set_idx: usize| {
+        set_exercises.update(|exs| {
+            if let Some(ex) = exs.get_mut(ex_idx) {
+                if set_idx < ex.sets_completed.len() {
+                    ex.sets_completed.remove(set_idx);
+                }
+            }
+        });
+        if rpe_pending.get() == Some(ex_idx) {
+            set_rpe_pending.set(None);
+        }
+        sync_after_set_edit();
+    };
+
+    // Queue edits move exercises around, so index-based state follows
+    // the exercise it pointed at rather than the slot
+    let restructure = move |edit: &dyn Fn(&mut Vec<ExerciseWorkoutState>)| {
+        let names: Vec<String> = exercises.with_untracked(|exs| exs.iter().map(|e| e.exercise.name.clone()).collect());
+        set_exercises.update(|exs| edit(exs));
+        let remap = |idx: usize| -> Option<usize> {
+            let name = names.get(idx)?;
+            exercises.with_untracked(|exs| exs.iter().position(|e| &e.exercise.name == name))
+        };
+        let curr = current_idx.get_untracked();
+        let last = exercises.with_untracked(|exs| exs.len().saturating_sub(1));
+        set_current_idx.set(remap(curr).unwrap_or(curr.min(last)));
+        set_rpe_pending.set(rpe_pending.get_untracked().and_then(remap));
+        set_warmup_dismissed.update(|d| *d = d.iter().filter_map(|&i| remap(i)).collect());
+        sync_after_set_edit();
+    };
+
+    // Do another exercise in an untouched slot (e.g. the rack is taken)
+    let swap_exercise = move |idx: usize, replacement: Exercise| {
+        let db = storage::load_data();
+        restructure(&|exs| {
+            let Some(slot) = exs.get(idx) else { return };
+            if !slot.sets_completed.is_empty() || exs.iter().any(|e| e.exercise.name == replacement.name) {
+                return;
+            }
+            let old_name = slot.exercise.name.clone();
+            let new_ex = slot.exercise.substituted_by(&replacement);
+            // Keep a legacy superset partner pointing at this slot
+            for e in exs.iter_mut() {
+                if e.exercise.superset_with.as_deref() == Some(old_name.as_str()) {
+                    e.exercise.superset_with = Some(new_ex.name.clone());
+                }
+            }
+            exs[idx] = storage::workout_state_for(&new_ex, &db);
+        });
+        set_picker.set(None);
+    };
+
+    // Ad-hoc exercise, queued right after the current exercise (or group)
+    let add_exercise = move |new_ex: Exercise| {
+        if exercises.with_untracked(|exs| exs.iter().any(|e| e.exercise.name == new_ex.name)) {
+            return;
+        }
+        let db = storage::load_data();
+        let curr = current_idx.get_untracked();
+        let at = exercises.with_untracked(|exs| {
+            exercise_blocks(exs).into_iter().find(|b| b.contains(&curr)).map(|b| b.end).unwrap_or(exs.len())
+        });
+        restructure(&|exs| exs.insert(at.min(exs.len()), storage::workout_state_for(&new_ex, &db)));
+        if is_finished.get_untracked() {
+            set_is_finished.set(false);
+            set_is_resting.set(false);
+            set_current_idx.set(at);
+        }
+        set_picker.set(None);
+    };
+
+    // Swap an untouched exercise (or group) with the untouched one above/below it
+    let move_block = move |idx: usize, up: bool| {
+        restructure(&|exs| {
+            let blocks = exercise_blocks(exs);
+            let Some(b) = blocks.iter().position(|r| r.start == idx) else { return };
+            let Some(o) = (if up { b.checked_sub(1) } else { Some(b + 1) }).filter(|&o| o < blocks.len()) else { return };
+            let (first, second) = (blocks[b.min(o)].clone(), blocks[b.max(o)].clone());
+            if exs[first.start..second.end].iter().any(|e| !e.sets_completed.is_empty()) {
+                return;
+            }
+            exs[first.start..second.end].rotate_left(first.len());
+        });
+    };
+
+    let open_picker = move |mode: ExercisePicker| {
+        set_picker_query.set(String::new());
+        set_picker_results.set(vec![]);
+        set_show_overview.set(false);
+        set_picker.set(Some(mode));
+    };
+
+    let run_picker_search = move || {
+        let query = picker_query.get_untracked();
+        if query.len() < 2 {
+            set_picker_results.set(vec![]);
+            return;
+        }
+        set_picker_searching.set(true);
+        spawn_local(async move {
+            set_picker_results.set(search_wger_exercises(&query).await.unwrap_or_default());
+            set_picker_searching.set(false);
+        });
+    };
+
+    let pick_exercise = move |ex: Exercise| match picker.get_untracked() {
+        Some(ExercisePicker::Swap(idx)) => swap_exercise(idx, ex),
+        Some(ExercisePicker::Add) => add_exercise(ex),
+        None => {}
+    };
+
+    // Swap and reorder controls for exercises not started yet
+    let queue_actions_view = move |idx: usize, exs: &[ExerciseWorkoutState]| {
+        if !exs[idx].sets_completed.is_empty() {
+            return view! { <span></span> }.into_view();
+        }
+        let blocks = exercise_blocks(exs);
+        let untouched = |r: &std::ops::Range<usize>| exs[r.clone()].iter().all(|e| e.sets_completed.is_empty());
+        let b = blocks.iter().position(|r| r.contains(&idx)).unwrap_or(0);
+        let movable = blocks[b].start == idx && untouched(&blocks[b]);
+        let can_up = movable && b > 0 && untouched(&blocks[b - 1]);
+        let can_down = movable && blocks.get(b + 1).is_some_and(untouched);
+        view! {
+            <div class="overview-queue-actions">
+                <button class="overview-queue-btn" on:click=move |_| open_picker(ExercisePicker::Swap(idx))>"Byt"</button>
+                {can_up.then(|| view! {
+                    <button class="overview-queue-btn" title="Flytta upp" on:click=move |_| move_block(idx, true)>"↑"</button>
+                })}
+                {can_down.then(|| view! {
+                    <button class="overview-queue-btn" title="Flytta ner" on:click=move |_| move_block(idx, false)>"↓"</button>
+                })}
+            </div>
+        }.into_view()
+    };
+
+    // Completed sets of one exercise, editable from the overview
+    let completed_sets_view = move |ex_idx: usize, ex: &ExerciseWorkoutState| {
+        if ex.sets_completed.is_empty() {
+            return view! { <span></span> }.into_view();
+        }
+        let is_bodyweight = ex.exercise.is_bodyweight || ex.exercise.is_cardio;
+        view! {
+            <div class="overview-set-list">
+                {ex.sets_completed.iter().enumerate().map(|(si, set)| {
+                    let row_class = if set.kind == SetKind::Warmup { "overview-set warmup" } else { "overview-set" };
+                    view! {
+                        <div class=row_class>
+                            <span class="overview-set-num">{si + 1}</span>
+                            {(!is_bodyweight).then(|| view! {
+                                <input type="number" step="0.5" class="overview-set-input"
+                                    value=format_weight_in(set.weight, unit)
+                                    on:change=move |ev| {
+                                        if let Ok(w) = event_target_value(&ev).replace(',', ".").parse::<f64>() {
+                                            let kg = unit.to_kg(w).max(0.0);
+                                            edit_set(ex_idx, si, &|s| s.weight = kg);
+                                        }
+                                    }
+                                />
+                                <span class="overview-set-unit">{unit.label()}" ×"</span>
+                            })}
+                            {set.distance_m().map(|m| view! {
+                                <input type="number" step="0.01" class="overview-set-input"
+                                    value=format!("{:.2}", m / 1000.0)
+                                    on:change=move |ev| {
+                                        if let Ok(km) = event_target_value(&ev).replace(',', ".").parse::<f64>() {
+                                            edit_set(ex_idx, si, &|s| {
+                                                if let Some(c) = s.cardio.as_mut() {
+                                                    c.distance_m = Some(km.max(0.0) * 1000.0);
+                                                }
+                                            });
+                                        }
+                                    }
+                                />
+                                <span class="overview-set-unit">"km"</span>
+                            })}
+                            {match set.duration_secs {
+                                Some(secs) => view! {
+                                    <input type="number" class="overview-set-input"
+                                        value=secs.to_string()
+                                        on:change=move |ev| {
+                                            if let Ok(d) = event_target_value(&ev).parse::<u32>() {
+                                                edit_set(ex_idx, si, &|s| s.duration_secs = Some(d));
+                                            }
+                                        }
+                                    />
+                                    <span class="overview-set-unit">"s"</span>
+                                }.into_view(),
+                                None => view! {
+                                    <input type="number" class="overview-set-input"
+                                        value=set.reps.to_string()
+                                        on:change=move |ev| {
+                                            if let Ok(r) = event_target_value(&ev).parse::<u8>() {
+                                                edit_set(ex_idx, si, &|s| s.reps = r);
+                                            }
+                                        }
+                                    />
+                                    <span class="overview-set-unit">"reps"</span>
+                                }.into_view(),
+                            }}
+                            <button class="overview-set-delete" title="Ta bort set" on:click=move |_| delete_set(ex_idx, si)>"✕"</button>
+                        </div>
+                    }
+                }).collect_view()}
+            </div>
+        }.into_view()
+    };
+
+    // EMOM/AMRAP/interval blocks: the current exercise's group and its format
+    let current_block = move || -> Option<(std::ops::Range<usize>, BlockFormat)> {
+        exercises.with(|exs| {
+            let idx = current_idx.get();
+            let range = exercise_blocks(exs).into_iter().find(|b| b.contains(&idx))?;
+            let format = exs[range.clone()].iter().find_map(|e| e.exercise.block)?;
+            Some((range, format))
+        })
+    };
+    // (first exercise of the block, ms timestamp) so jumping to another block doesn't inherit the clock
+    let (block_started_at, set_block_started_at) = create_signal(Option::<(usize, f64)>::None);
+    let (block_logged, set_block_logged) = create_signal(Option::<u32>::None);  // interval already logged
+    let (block_reps, set_block_reps) = create_signal(String::new());
+    // None = not started, Some(None) = time's up
+    let block_phase = create_memo(move |_| {
+        let _ = elapsed.get();
+        let (range, format) = current_block()?;
+        let (_, started) = block_started_at.get().filter(|(first, _)| *first == range.start)?;
+        Some(format.phase_at(((js_sys::Date::now() - started) / 1000.0).max(0.0) as u32))
+    });
+    // Changes only when the interval or work/rest flips, not every second
+    let block_step = create_memo(move |_| block_phase.get().map(|p| p.map(|p| (p.interval, p.working))));
+
+    create_effect(move |prev: Option<Option<Option<(u32, bool)>>>| {
+        let step = block_step.get();
+        if matches!(prev, Some(Some(Some(_)))) && prev != Some(step) {
+            rest_done_alert(audio_ctx);
+        }
+        step
+    });
+
+    // One set on `idx` inside a block; timed members log the interval's work time
+    let log_block_set = move |idx: usize, reps: u8, work_secs: u32| {
+        let now = js_sys::Date::now() as i64 / 1000;
+        set_exercises.update(|exs| {
+            if let Some(ex) = exs.get_mut(idx) {
+                let timed = ex.exercise.duration_secs.is_some();
+                ex.sets_completed.push(SetRecord {
+                    weight: ex.current_weight,
+                    reps: if timed { 0 } else { reps },
+                    timestamp: now,
+                    rest_before_secs: None,
+                    rpe: None,
+                    kind: SetKind::Working,
+                    rest_target_secs: None,
+                    duration_secs: timed.then_some(work_secs),
+                    cardio: None,
+                });
+            }
+        });
+        set_last_set_time.set(now);
+    };
+
+    let start_block = move || {
+        let Some((range, _)) = current_block() else { return };
+        unlock_audio(audio_ctx);
+        set_block_logged.set(None);
+        set_block_reps.set(String::new());
+        set_block_started_at.set(Some((range.start, js_sys::Date::now())));
+    };
+
+    // Whatever was logged is what the block consisted of; move on past the group
+    let finish_block = move || {
+        let Some((range, _)) = current_block() else { return };
+        set_block_started_at.set(None);
+        set_exercises.update(|exs| {
+            for ex in &mut exs[range.clone()] {
+                ex.exercise.sets = ex.working_sets_done() as u8;
+            }
+        });
+        match exercises.with_untracked(|exs| (range.end..exs.len()).find(|&i| !exs[i].is_done())) {
+            Some(next) => set_current_idx.set(next),
+            None => set_is_finished.set(true),
+        }
+        sync_after_set_edit();
+    };
+
+    // EMOM/intervals: the member whose turn it is logs the reps typed in (or its target)
+    let log_block_interval = move || {
+        let (Some((range, format)), Some(Some(phase))) = (current_block(), block_phase.get_untracked()) else { return };
+        if block_logged.get_untracked() == Some(phase.interval) {
+            return;
+        }
+        let idx = range.start + phase.interval as usize % range.len();
+        let target = exercises.with_untracked(|exs| parse_target_reps(&exs[idx].exercise.reps_target));
+        let reps = block_reps.get_untracked().parse::<u8>().unwrap_or(target);
+        let work_secs = match format {
+            BlockFormat::Interval { work_secs, .. } => work_secs,
+            _ => exercises.with_untracked(|exs| exs[idx].exercise.duration_secs.unwrap_or(60)),
+        };
+        log_block_set(idx, reps, work_secs);
+        set_block_logged.set(Some(phase.interval));
+        set_block_reps.set(String::new());
+    };
+
+    // AMRAP: a finished round is one set of each member at its target
+    let log_block_round = move || {
+        let Some((range, _)) = current_block() else { return };
+        for idx in range {
+            let (reps, secs) = exercises.with_untracked(|exs| {
+                (parse_target_reps(&exs[idx].exercise.reps_target), exs[idx].exercise.duration_secs.unwrap_or(0))
+            });
+            log_block_set(idx, reps, secs);
+        }
+    };
+
+    let has_sets = move || exercises.with(|exs| exs.iter().any(|e| !e.sets_completed.is_empty()));
+
     view! {
         <div class="workout">
             <div class="workout-header">
@@ -253,9 +871,9 @@ pub fn WorkoutActive(
                     {move || {
                         let curr = current_idx.get();
                         let exs = exercises.get();
-                        (0..total_exercises).map(|i| {
+                        (0..exs.len()).map(|i| {
                             let is_done = exs.get(i).map(|e| {
-                                e.sets_completed.len() >= e.exercise.sets as usize
+                                e.is_done()
                             }).unwrap_or(false);
                             let is_current = i == curr;
                             let is_started = exs.get(i).map(|e| !e.sets_completed.is_empty()).unwrap_or(false);
@@ -291,90 +909,50 @@ pub fn WorkoutActive(
                                     <button class="overview-close" on:click=move |_| set_show_overview.set(false)>"✕"</button>
                                 </div>
                                 <div class="overview-list">
-                                    {
-                                        let mut result: Vec<View> = Vec::new();
-                                        let mut i = 0;
-                                        while i < exs.len() {
-                                            let ex = &exs[i];
-                                            let is_superset = ex.exercise.is_superset;
-
-                                            let has_partner = is_superset && i + 1 < exs.len() &&
-                                                exs[i + 1].exercise.is_superset &&
-                                                exs[i + 1].exercise.superset_with.as_ref() == Some(&ex.exercise.name);
-
-                                            if has_partner {
-                                                let ex1 = &exs[i];
-                                                let ex2 = &exs[i + 1];
-                                                let idx1 = i;
-                                                let idx2 = i + 1;
-
-                                                let item1_class = if ex1.sets_completed.len() >= ex1.exercise.sets as usize {
-                                                    "overview-item done"
-                                                } else if idx1 == curr {
-                                                    "overview-item current"
-                                                } else {
-                                                    "overview-item"
-                                                };
-                                                let item2_class = if ex2.sets_completed.len() >= ex2.exercise.sets as usize {
-                                                    "overview-item done"
-                                                } else if idx2 == curr {
-                                                    "overview-item current"
-                                                } else {
-                                                    "overview-item"
-                                                };
-
-                                                let icon1 = if ex1.sets_completed.len() >= ex1.exercise.sets as usize { "✓" }
-                                                    else if idx1 == curr { "►" } else { "" };
-                                                let icon2 = if ex2.sets_completed.len() >= ex2.exercise.sets as usize { "✓" }
-                                                    else if idx2 == curr { "►" } else { "" };
-
-                                                let name1 = ex1.exercise.name.clone();
-                                                let name2 = ex2.exercise.name.clone();
-                                                let sets1 = format!("{}/{}", ex1.sets_completed.len(), ex1.exercise.sets);
-                                                let sets2 = format!("{}/{}", ex2.sets_completed.len(), ex2.exercise.sets);
-
-                                                result.push(view! {
-                                                    <div class="superset-group">
-                                                        <button class=item1_class on:click=move |_| jump_to_exercise(idx1)>
-                                                            <span class="overview-icon">{icon1}</span>
-                                                            <span class="overview-name">{name1}</span>
-                                                            <span class="overview-sets">{sets1}</span>
-                                                        </button>
-                                                        <button class=item2_class on:click=move |_| jump_to_exercise(idx2)>
-                                                            <span class="overview-icon">{icon2}</span>
-                                                            <span class="overview-name">{name2}</span>
-                                                            <span class="overview-sets">{sets2}</span>
-                                                        </button>
-                                                    </div>
-                                                }.into_view());
-                                                i += 2;
+                                    {exercise_blocks(&exs).into_iter().map(|block| {
+                                        let grouped = block.len() > 1;
+                                        let label = format!("{} · {} varv", group_label(block.len()),
+                                            exs[block.clone()].iter().map(|e| e.exercise.sets).max().unwrap_or(0));
+                                        let items = block.map(|idx| {
+                                            let ex = &exs[idx];
+                                            let item_class = if ex.is_done() {
+                                                "overview-item done"
+                                            } else if idx == curr {
+                                                "overview-item current"
                                             } else {
-                                                let idx = i;
-                                                let item_class = if ex.sets_completed.len() >= ex.exercise.sets as usize {
-                                                    "overview-item done"
-                                                } else if idx == curr {
-                                                    "overview-item current"
-                                                } else {
-                                                    "overview-item"
-                                                };
-                                                let icon = if ex.sets_completed.len() >= ex.exercise.sets as usize { "✓" }
-                                                    else if idx == curr { "►" } else { "" };
-                                                let name = ex.exercise.name.clone();
-                                                let sets = format!("{}/{}", ex.sets_completed.len(), ex.exercise.sets);
-
-                                                result.push(view! {
-                                                    <button class=item_class on:click=move |_| jump_to_exercise(idx)>
-                                                        <span class="overview-icon">{icon}</span>
-                                                        <span class="overview-name">{name}</span>
-                                                        <span class="overview-sets">{sets}</span>
-                                                    </button>
-                                                }.into_view());
-                                                i += 1;
+                                                "overview-item"
+                                            };
+                                            let icon = if ex.is_done() { "✓" }
+                                                else if idx == curr { "►" } else { "" };
+                                            let name = ex.exercise.name.clone();
+                                            let sets = format!("{}/{}", ex.working_sets_done(), ex.exercise.sets);
+                                            let list = completed_sets_view(idx, ex);
+                                            let actions = queue_actions_view(idx, &exs);
+                                            view! {
+                                                <button class=item_class on:click=move |_| jump_to_exercise(idx)>
+                                                    <span class="overview-icon">{icon}</span>
+                                                    <span class="overview-name">{name}</span>
+                                                    <span class="overview-sets">{sets}</span>
+                                                </button>
+                                                {list}
+                                                {actions}
                                             }
+                                        }).collect_view();
+                                        if grouped {
+                                            view! {
+                                                <div class="superset-group">
+                                                    <div class="superset-group-label">{label}</div>
+                                                    {items}
+                                                </div>
+                                            }.into_view()
+                                        } else {
+                                            items
                                         }
-                                        result.into_iter().collect_view()
-                                    }
+                                    }).collect_view()}
                                 </div>
+                                <button class="overview-add-btn" on:click=move |_| open_picker(ExercisePicker::Add)>
+                                    "+ Lägg till övning"
+                                </button>
                             </div>
                         </div>
                     }.into_view()
@@ -390,7 +968,7 @@ pub fn WorkoutActive(
 
                         let total_volume: f64 = exercises.get().iter()
                             .flat_map(|ex| ex.sets_completed.iter())
-                            .map(|set| set.weight * set.reps as f64)
+                            .map(|set| set.volume())
                             .sum();
 
                         let efficiency = if duration_mins > 0 {
@@ -433,11 +1011,33 @@ pub fn WorkoutActive(
                                 <div class="finish-title">"Bra jobbat!"</div>
                                 <div class="finish-time">{format_time(elapsed.get())}</div>
                                 <div class="finish-stats">
-                                    <span class="finish-stat">{format!("{:.0} kg volym", total_volume)}</span>
+                                    <span class="finish-stat">{format!("{:.0} {} volym", unit.in_unit(total_volume), unit.label())}</span>
                                     <span class="finish-stat">{format!("{} kcal", calories)}</span>
                                 </div>
 
-                                {move || if is_saving.get() {
+                                {move || {
+                                    let prs = new_prs.get();
+                                    (!prs.is_empty()).then(|| view! {
+                                        <div class="finish-prs">
+                                            <div class="finish-prs-title">{format!("🏆 {} nya rekord", prs.len())}</div>
+                                            {prs.iter().map(|pr| view! {
+                                                <div class="finish-pr">
+                                                    <span class="finish-pr-name">{pr.exercise_name.clone()}</span>
+                                                    <span class="finish-pr-kind">{pr.kind.label()}</span>
+                                                    <span class="finish-pr-value">{format_pr_value(pr, unit)}</span>
+                                                </div>
+                                            }).collect_view()}
+                                        </div>
+                                    })
+                                }}
+
+                                {move || if is_saved.get() {
+                                    view! {
+                                        <button class="finish-save-btn" on:click=move |_| set_view.set(AppView::Dashboard)>
+                                            "Klar"
+                                        </button>
+                                    }.into_view()
+                                } else if is_saving.get() {
                                     view! {
                                         <div class="saving-indicator">"Sparar..."</div>
                                     }.into_view()
@@ -449,21 +1049,29 @@ pub fn WorkoutActive(
                                             supabase::clear_sync_failed();
 
                                             let exs = exercises.get();
+                                            let db = storage::load_data();
                                             let records: Vec<ExerciseRecord> = exs.iter()
                                                 .filter(|e| !e.sets_completed.is_empty())
                                                 .map(|e| ExerciseRecord {
                                                     name: e.exercise.name.clone(),
+                                                    exercise_id: db.id_of(&e.exercise),
                                                     sets: e.sets_completed.clone(),
                                                     primary_muscles: e.exercise.primary_muscles.clone(),
                                                     secondary_muscles: e.exercise.secondary_muscles.clone(),
+                                                    is_cardio: e.exercise.is_cardio,
                                                 })
                                                 .collect();
-                                            storage::save_session(routine_name_sig.get(), records, elapsed.get());
+                                            let prs = storage::save_session(routine_name_sig.get(), records, elapsed.get());
+                                            let celebrate = !prs.is_empty();
+                                            set_new_prs.set(prs);
 
                                             use gloo_timers::callback::Interval;
                                             let check_count = std::rc::Rc::new(std::cell::RefCell::new(0));
                                             let check_count_clone = check_count.clone();
                                             let interval = Interval::new(500, move || {
+                                                if is_saved.get_untracked() {
+                                                    return;
+                                                }
                                                 *check_count_clone.borrow_mut() += 1;
                                                 let count = *check_count_clone.borrow();
 
@@ -474,13 +1082,21 @@ pub fn WorkoutActive(
                                                 }
 
                                                 if count >= 10 {
-                                                    set_view.set(AppView::Dashboard);
+                                                    if celebrate {
+                                                        set_is_saving.set(false);
+                                                        set_is_saved.set(true);
+                                                    } else {
+                                                        set_view.set(AppView::Dashboard);
+                                                    }
                                                 }
                                             });
                                             leptos::on_cleanup(move || drop(interval));
                                         }>
                                             "Spara pass"
                                         </button>
+                                        <button class="undo-set-btn" on:click=move |_| undo_last_set()>
+                                            "↶ Ångra senaste set"
+                                        </button>
                                     }.into_view()
                                 }}
 
@@ -498,7 +1114,40 @@ pub fn WorkoutActive(
                         view! {
                             <div class="rest-screen">
                                 <div class="rest-label">"VILA"</div>
-                                <div class="rest-timer">{move || format_time(rest_elapsed.get())}</div>
+                                {move || {
+                                    let remaining = rest_target() - rest_elapsed.get();
+                                    if remaining > 0 {
+                                        view! { <div class="rest-timer">{format_time(remaining)}</div> }
+                                    } else {
+                                        view! { <div class="rest-timer over">{format!("+{}", format_time(-remaining))}</div> }
+                                    }
+                                }}
+                                <div class="rest-target-row">
+                                    <button class="rest-adjust" on:click=move |_| set_rest_adjust.update(|a| *a -= 15)>"−15s"</button>
+                                    <span class="rest-target">{move || format!("Mål {}", format_time(rest_target()))}</span>
+                                    <button class="rest-adjust" on:click=move |_| set_rest_adjust.update(|a| *a += 15)>"+15s"</button>
+                                </div>
+                                {move || rpe_pending.get().map(|rated_idx| {
+                                    let selected = exercises.get().get(rated_idx)
+                                        .and_then(|e| e.sets_completed.last())
+                                        .and_then(|s| s.rpe);
+                                    view! {
+                                        <div class="rpe-picker">
+                                            <div class="rpe-label">"Hur tungt? (RPE)"</div>
+                                            <div class="rpe-buttons">
+                                                {RPE_SCALE.into_iter().map(|rpe| {
+                                                    let btn_class = if selected == Some(rpe) { "rpe-button selected" } else { "rpe-button" };
+                                                    view! {
+                                                        <button class=btn_class on:click=move |_| rate_last_set(rated_idx, rpe)>
+                                                            <span class="rpe-value">{format_weight(rpe)}</span>
+                                                            <span class="rpe-rir">{format!("{} kvar", format_weight(rpe_to_rir(rpe)))}</span>
+                                                        </button>
+                                                    }
+                                                }).collect_view()}
+                                            </div>
+                                        </div>
+                                    }
+                                })}
                                 <div class="rest-next">
                                     <span class="rest-next-label">"Nästa:"</span>
                                     <span class="rest-next-exercise">{next_name}</span>
@@ -507,22 +1156,219 @@ pub fn WorkoutActive(
                                 <button class="rest-continue-btn" on:click=continue_workout>
                                     "Fortsätt"
                                 </button>
+                                <button class="undo-set-btn" on:click=move |_| undo_last_set()>
+                                    "↶ Ångra senaste set"
+                                </button>
+                            </div>
+                        }.into_view()
+                    } else if let Some((range, format)) = current_block() {
+                        let names = exercises.with(|exs| {
+                            exs[range.clone()].iter().map(|e| e.exercise.name.clone()).collect::<Vec<_>>()
+                        });
+                        let members = names.join(" · ");
+                        let (first, count) = (range.start, range.len());
+                        let is_amrap = matches!(format, BlockFormat::Amrap { .. });
+
+                        view! {
+                            <div class="exercise-screen block-screen">
+                                <div class="exercise-progress">{format.label()}</div>
+                                <div class="block-members">{members}</div>
+
+                                {move || match block_phase.get() {
+                                    None => view! {
+                                        <button class="block-start-btn" on:click=move |_| start_block()>"▶ Starta block"</button>
+                                    }.into_view(),
+                                    Some(Some(phase)) => {
+                                        let label = match format {
+                                            BlockFormat::Emom { minutes } => format!("Minut {} av {}", phase.interval + 1, minutes),
+                                            BlockFormat::Amrap { .. } => "Tid kvar".to_string(),
+                                            BlockFormat::Interval { rounds, .. } => format!(
+                                                "{} · Runda {} av {}",
+                                                if phase.working { "ARBETA" } else { "VILA" }, phase.interval + 1, rounds
+                                            ),
+                                        };
+                                        let clock_class = if phase.working { "block-clock" } else { "block-clock resting" };
+                                        view! {
+                                            <div class="block-phase">{label}</div>
+                                            <div class=clock_class>{format_time(phase.remaining_secs as i64)}</div>
+                                        }.into_view()
+                                    }
+                                    Some(None) => view! {
+                                        <div class="block-phase">"Blocket klart"</div>
+                                    }.into_view(),
+                                }}
+
+                                {move || match block_step.get() {
+                                    Some(Some(_)) if is_amrap => {
+                                        let rounds = exercises.with(|exs| exs[first].working_sets_done());
+                                        view! {
+                                            <div class="block-rounds">{format!("{} varv", rounds)}</div>
+                                            <button class="block-log-btn" on:click=move |_| log_block_round()>"Varv klart ✓"</button>
+                                        }.into_view()
+                                    }
+                                    Some(Some((interval, _))) => {
+                                        let idx = first + interval as usize % count;
+                                        let (name, target, weight, loaded) = exercises.with(|exs| {
+                                            let e = &exs[idx];
+                                            let loaded = !e.exercise.is_bodyweight && e.current_weight > 0.0;
+                                            (e.exercise.name.clone(), e.exercise.reps_target.clone(), e.current_weight, loaded)
+                                        });
+                                        let logged = move || block_logged.get() == Some(interval);
+                                        view! {
+                                            <div class="exercise-name-big">{name}</div>
+                                            {loaded.then(|| view! { <div class="block-weight">{display_weight(weight, unit)}</div> })}
+                                            <div class="block-log-row">
+                                                <input type="number" inputmode="numeric" class="cardio-input block-reps-input"
+                                                    placeholder=target
+                                                    prop:value=block_reps
+                                                    on:input=move |e| set_block_reps.set(event_target_value(&e))
+                                                />
+                                                <button class="block-log-btn" disabled=logged on:click=move |_| log_block_interval()>
+                                                    {move || if logged() { "Loggat ✓" } else { "Klar" }}
+                                                </button>
+                                            </div>
+                                        }.into_view()
+                                    }
+                                    _ => {
+                                        let sets: usize = exercises.with(|exs| exs[range.clone()].iter().map(|e| e.working_sets_done()).sum());
+                                        view! {
+                                            <div class="block-rounds">{format!("{} set loggade", sets)}</div>
+                                        }.into_view()
+                                    }
+                                }}
+
+                                <button class="skip-exercise-btn" on:click=move |_| finish_block()>
+                                    {move || match block_phase.get() {
+                                        Some(Some(_)) => "Avsluta block →",
+                                        Some(None) => "Fortsätt →",
+                                        None => "Hoppa över block →",
+                                    }}
+                                </button>
+                                {move || has_sets().then(|| view! {
+                                    <button class="undo-set-btn" on:click=move |_| {
+                                        set_block_logged.set(None);
+                                        undo_last_set();
+                                    }>
+                                        "↶ Ångra senaste set"
+                                    </button>
+                                })}
+                            </div>
+                        }.into_view()
+                    } else if current_exercise().is_some_and(|e| e.exercise.is_cardio) {
+                        let ex_name = current_exercise().map(|e| e.exercise.name.clone()).unwrap_or_default();
+                        let target = current_exercise().map(|e| e.exercise.reps_target.clone()).unwrap_or_default();
+                        let last_hint = storage::load_data().last_exercise_record(&ex_name).and_then(|r| {
+                            let (distance, secs) = r.distance_and_time();
+                            let pace = r.pace_secs_per_km()?;
+                            Some(format!("Senast: {:.2} km · {} · {}", distance / 1000.0, format_time(secs as i64), format_pace(pace)))
+                        });
+
+                        view! {
+                            <div class="exercise-screen cardio-screen">
+                                <div class="exercise-progress">
+                                    {move || format!("Intervall {} av {}", current_set_num(), total_sets())}
+                                </div>
+                                <div class="bodyweight-indicator">"KONDITION"</div>
+                                <div class="exercise-name-big">{ex_name}</div>
+                                {(!target.is_empty()).then(|| view! {
+                                    <div class="exercise-hint">{format!("Mål: {}", target)}</div>
+                                })}
+                                {last_hint.map(|hint| view! { <div class="weight-note">{hint}</div> })}
+
+                                <button class="cardio-clock-btn" on:click=move |_| toggle_cardio_clock()>
+                                    {move || match cardio_clock.get() {
+                                        Some(start) => {
+                                            let _ = elapsed.get();
+                                            format!("■ {}", format_time(((js_sys::Date::now() - start) / 1000.0) as i64))
+                                        }
+                                        None => "▶ Starta klocka".to_string(),
+                                    }}
+                                </button>
+
+                                <div class="cardio-grid">
+                                    <div class="cardio-field">
+                                        <span class="cardio-label">"Distans (km)"</span>
+                                        <input type="number" inputmode="decimal" step="0.01" class="cardio-input"
+                                            prop:value=cardio_km
+                                            on:input=move |e| set_cardio_km.set(event_target_value(&e))
+                                        />
+                                    </div>
+                                    <div class="cardio-field">
+                                        <span class="cardio-label">"Tid (min : s)"</span>
+                                        <div class="cardio-time">
+                                            <input type="number" inputmode="numeric" class="cardio-input" placeholder="min"
+                                                prop:value=cardio_min
+                                                on:input=move |e| set_cardio_min.set(event_target_value(&e))
+                                            />
+                                            <input type="number" inputmode="numeric" class="cardio-input" placeholder="s"
+                                                prop:value=cardio_sec
+                                                on:input=move |e| set_cardio_sec.set(event_target_value(&e))
+                                            />
+                                        </div>
+                                    </div>
+                                    <div class="cardio-field">
+                                        <span class="cardio-label">"Kalorier"</span>
+                                        <input type="number" inputmode="numeric" class="cardio-input" placeholder="–"
+                                            prop:value=cardio_kcal
+                                            on:input=move |e| set_cardio_kcal.set(event_target_value(&e))
+                                        />
+                                    </div>
+                                    <div class="cardio-field">
+                                        <span class="cardio-label">"Snittpuls"</span>
+                                        <input type="number" inputmode="numeric" class="cardio-input" placeholder="–"
+                                            prop:value=cardio_hr
+                                            on:input=move |e| set_cardio_hr.set(event_target_value(&e))
+                                        />
+                                    </div>
+                                </div>
+
+                                <div class="cardio-pace">
+                                    {move || {
+                                        let secs = cardio_secs();
+                                        cardio_distance_m()
+                                            .filter(|_| secs > 0)
+                                            .map(|d| format!("Tempo {}", format_pace(secs as f64 / (d / 1000.0))))
+                                    }}
+                                </div>
+
+                                <button class="cardio-save-btn" on:click=move |_| save_cardio_interval()>
+                                    "Spara intervall"
+                                </button>
+                                <button class="skip-exercise-btn" on:click=skip_exercise>
+                                    "Hoppa över övning →"
+                                </button>
+                                {move || has_sets().then(|| view! {
+                                    <button class="undo-set-btn" on:click=move |_| undo_last_set()>
+                                        "↶ Ångra senaste set"
+                                    </button>
+                                })}
                             </div>
                         }.into_view()
                     } else {
                         let ex = current_exercise();
                         let ex_name = ex.as_ref().map(|e| e.exercise.name.clone()).unwrap_or_default();
-                        let is_superset = ex.as_ref().map(|e| e.exercise.is_superset).unwrap_or(false);
+                        // "Tri-set → Next exercise" while others in the group still have sets left
+                        let group_info = exercises.with_untracked(|exs| {
+                            let idx = current_idx.get_untracked();
+                            let members = group_members(exs, idx);
+                            (members.len() > 1).then(|| {
+                                let next = next_in_group(exs, idx)
+                                    .filter(|&n| n != idx)
+                                    .map(|n| format!(" → {}", exs[n].exercise.name))
+                                    .unwrap_or_default();
+                                format!("{}{}", group_label(members.len()), next)
+                            })
+                        });
                         let is_bodyweight = ex.as_ref().map(|e| e.exercise.is_bodyweight).unwrap_or(false);
                         let is_timed = ex.as_ref().and_then(|e| e.exercise.duration_secs).is_some();
                         let target_duration = ex.as_ref().and_then(|e| e.exercise.duration_secs).unwrap_or(30);
-                        let ss_with = ex.as_ref().and_then(|e| e.exercise.superset_with.clone());
                         let is_dumbbell = matches!(ex_name.as_str(), "Hammercurls" | "Sidolyft");
                         let is_alternating = matches!(ex_name.as_str(), "Utfallssteg" | "Dead Bug");
 
+                        let weight_note = ex.as_ref().and_then(|e| e.weight_note.clone());
+
                         let last_duration = ex.as_ref()
-                            .and_then(|e| e.last_data.as_ref())
-                            .map(|d| d.reps as u32);
+                            .and_then(|e| storage::load_data().last_duration(&e.exercise.name));
 
                         view! {
                             <div class=move || if show_timer_flash.get() { "exercise-screen timer-flash" } else { "exercise-screen" }>
@@ -530,10 +1376,8 @@ pub fn WorkoutActive(
                                     {move || format!("Set {} av {}", current_set_num(), total_sets())}
                                 </div>
 
-                                {is_superset.then(|| view! {
-                                    <div class="superset-indicator">
-                                        "Superset → " {ss_with.unwrap_or_default()}
-                                    </div>
+                                {group_info.map(|info| view! {
+                                    <div class="superset-indicator">{info}</div>
                                 })}
 
                                 {is_bodyweight.then(|| view! {
@@ -542,7 +1386,9 @@ pub fn WorkoutActive(
                                     </div>
                                 })}
 
-                                <div class="exercise-name-big">{ex_name}</div>
+                                <button class="exercise-name-big exercise-name-link" title="Visa historik" on:click=move |_| open_exercise_history()>
+                                    {ex_name}
+                                </button>
 
                                 {is_dumbbell.then(|| view! {
                                     <div class="exercise-hint">"Lägg ihop båda hantlarnas vikt"</div>
@@ -554,27 +1400,112 @@ pub fn WorkoutActive(
 
                                 {(!is_bodyweight).then(|| view! {
                                     <div class="weight-section">
-                                        <button class="weight-adjust" on:click=move |_| adjust_weight(-2.5)>
+                                        <button class="weight-adjust" on:click=move |_| adjust_weight(-1.0)>
                                             "−"
                                         </button>
                                         <div class="weight-display-big">
-                                            <span class="weight-value">{move || format_weight(current_weight())}</span>
-                                            <span class="weight-unit">"kg"</span>
+                                            <span class="weight-value">{move || format_weight_in(current_weight(), unit)}</span>
+                                            <span class="weight-unit">{unit.label()}</span>
                                         </div>
-                                        <button class="weight-adjust" on:click=move |_| adjust_weight(2.5)>
+                                        <button class="weight-adjust" on:click=move |_| adjust_weight(1.0)>
                                             "+"
                                         </button>
                                     </div>
                                 })}
 
+                                {(!is_bodyweight).then(|| view! {
+                                    <div class="weight-extras">
+                                        {weight_note.map(|note| view! {
+                                            <div class="weight-note">{note}</div>
+                                        })}
+                                        <button class="plate-toggle" on:click=move |_| set_show_plates.update(|v| *v = !*v)>
+                                            {move || if show_plates.get() { "Dölj skivor" } else { "Skivor ▸" }}
+                                        </button>
+                                    </div>
+                                })}
+
+                                {move || (show_plates.get() && !is_bodyweight).then(|| {
+                                    let target = current_weight();
+                                    let load = plate_settings.with_value(|s| plates::load_for(target, s));
+                                    let bar = plate_settings.with_value(|s| s.bar_weight);
+                                    let loadable = load.total;
+                                    let off_target = (loadable - target).abs() > 0.001;
+                                    view! {
+                                        <div class="plate-calc">
+                                            <div class="plate-calc-label">{format!("Per sida · stång {}", display_weight(bar, unit))}</div>
+                                            <div class="plate-list">
+                                                {if load.per_side.is_empty() {
+                                                    view! { <span class="plate-empty">"Bara stången"</span> }.into_view()
+                                                } else {
+                                                    load.per_side.iter().map(|&p| view! {
+                                                        <span class="plate-chip">{format_weight_in(p, unit)}</span>
+                                                    }).collect_view()
+                                                }}
+                                            </div>
+                                            {off_target.then(|| view! {
+                                                <button class="plate-snap" on:click=move |_| set_weight(loadable)>
+                                                    {format!("Går inte att lasta → {}", display_weight(loadable, unit))}
+                                                </button>
+                                            })}
+                                        </div>
+                                    }
+                                })}
+
+                                {move || {
+                                    let ex = current_exercise()?;
+                                    if ex.exercise.is_bodyweight
+                                        || ex.exercise.duration_secs.is_some()
+                                        || ex.working_sets_done() > 0
+                                        || warmup_dismissed.get().contains(&current_idx.get())
+                                    {
+                                        return None;
+                                    }
+                                    let ramp = plate_settings.with_value(|s| plates::warmup_ramp(
+                                        ex.current_weight, ex.exercise.warmup, ex.exercise.is_barbell(), ex.exercise.weight_step(unit), s,
+                                    ));
+                                    let done = ex.sets_completed.iter().filter(|s| s.kind == SetKind::Warmup).count();
+                                    if done >= ramp.len() {
+                                        return None;
+                                    }
+                                    Some(view! {
+                                        <div class="warmup-card">
+                                            <div class="warmup-title">"Uppvärmning"</div>
+                                            <div class="warmup-steps">
+                                                {ramp.into_iter().enumerate().map(|(i, (w, r))| {
+                                                    let step_class = if i < done {
+                                                        "warmup-step done"
+                                                    } else if i == done {
+                                                        "warmup-step next"
+                                                    } else {
+                                                        "warmup-step"
+                                                    };
+                                                    view! {
+                                                        <button class=step_class disabled=i != done on:click=move |_| log_warmup_set(w, r)>
+                                                            {format!("{} × {}", format_weight_in(w, unit), r)}
+                                                        </button>
+                                                    }
+                                                }).collect_view()}
+                                            </div>
+                                            <button class="warmup-skip" on:click=move |_| {
+                                                set_warmup_dismissed.update(|d| d.push(current_idx.get()));
+                                            }>
+                                                "Hoppa över uppvärmning"
+                                            </button>
+                                        </div>
+                                    })
+                                }}
+
                                 {is_timed.then(|| view! {
                                     <div class="timer-section">
                                         {move || if timer_running.get() {
                                             view! {
                                                 <div class="timer-countdown">
                                                     <div class="timer-display">
-                                                        {move || format!("0:{:02}", timer_remaining.get())}
+                                                        {move || format_time(timer_remaining.get() as i64)}
                                                     </div>
+                                                    <button class="timer-done-btn" on:click=move |_| complete_timed_set()>
+                                                        "Klar nu"
+                                                    </button>
                                                     <button class="timer-stop-btn" on:click=move |_| {
                                                         set_timer_running.set(false);
                                                         set_timer_remaining.set(0);
@@ -588,7 +1519,7 @@ pub fn WorkoutActive(
                                                 <div class="timer-selector">
                                                     <div class="timer-label">"Välj tid:"</div>
                                                     <div class="duration-buttons">
-                                                        {[20u32, 25, 30, 35, 40, 45, 50, 55].into_iter().map(|d| {
+                                                        {duration_presets(target_duration, last_duration).into_iter().map(|d| {
                                                             let is_target = d == target_duration;
                                                             let is_last = last_duration == Some(d);
                                                             let btn_class = move || {
@@ -608,7 +1539,7 @@ pub fn WorkoutActive(
                                                                     class=btn_class
                                                                     on:click=move |_| set_timer_selected_duration.set(d)
                                                                 >
-                                                                    {format!("{}s", d)}
+                                                                    {format_duration(d)}
                                                                 </button>
                                                             }
                                                         }).collect_view()}
@@ -617,7 +1548,7 @@ pub fn WorkoutActive(
                                                         "▶ STARTA"
                                                     </button>
                                                     <div class="timer-target-hint">
-                                                        {format!("Mål: {} sek", target_duration)}
+                                                        {format!("Mål: {}", format_duration(target_duration))}
                                                     </div>
                                                 </div>
                                             }.into_view()
@@ -625,6 +1556,22 @@ pub fn WorkoutActive(
                                     </div>
                                 })}
 
+                                {(!is_timed).then(|| view! {
+                                    <div class="set-kind-row">
+                                        {SetKind::all().into_iter().map(|kind| {
+                                            let btn_class = move || if next_set_kind.get() == kind {
+                                                "set-kind-button selected"
+                                            } else {
+                                                "set-kind-button"
+                                            };
+                                            view! {
+                                                <button class=btn_class on:click=move |_| set_next_set_kind.set(kind)>
+                                                    {kind.label()}
+                                                </button>
+                                            }
+                                        }).collect_view()}
+                                    </div>
+                                })}
                                 {(!is_timed).then(|| view! {
                                     <div class="rep-label">"Tryck antal reps:"</div>
                                 })}
@@ -656,7 +1603,7 @@ pub fn WorkoutActive(
                                                 view! {
                                                     <button
                                                         class=btn_class
-                                                        on:click=move |_| complete_set(r)
+                                                        on:click=move |_| complete_set(r, None, None)
                                                     >
                                                         {r}
                                                     </button>
@@ -674,6 +1621,16 @@ pub fn WorkoutActive(
                                 <button class="skip-exercise-btn" on:click=skip_exercise>
                                     "Hoppa över övning →"
                                 </button>
+                                {move || current_exercise().is_some_and(|e| e.sets_completed.is_empty()).then(|| view! {
+                                    <button class="skip-exercise-btn" on:click=move |_| open_picker(ExercisePicker::Swap(current_idx.get()))>
+                                        "Byt övning ⇄"
+                                    </button>
+                                })}
+                                {move || has_sets().then(|| view! {
+                                    <button class="undo-set-btn" on:click=move |_| undo_last_set()>
+                                        "↶ Ångra senaste set"
+                                    </button>
+                                })}
                             </div>
                         }.into_view()
                     }
@@ -682,14 +1639,7 @@ pub fn WorkoutActive(
 
             <div class="workout-footer">
                 <button class="back-btn" on:click=move |_| {
-                    let paused = crate::types::PausedWorkout {
-                        routine_name: routine_name_pause.clone(),
-                        exercises: exercises.get(),
-                        current_exercise_idx: current_idx.get(),
-                        start_timestamp: start_time.get(),
-                        elapsed_secs: elapsed.get(),
-                    };
-                    let _ = storage::save_paused_workout(&paused);
+                    let _ = storage::save_paused_workout(&snapshot());
                     set_view.set(AppView::Dashboard);
                 }>
                     <span class="pause-icon"></span>" Pausa"
@@ -701,6 +1651,80 @@ pub fn WorkoutActive(
                 </button>
             </div>
 
+            {move || picker.get().map(|mode| {
+                let (title, alternatives) = match mode {
+                    ExercisePicker::Swap(idx) => {
+                        let alts = exercises.with_untracked(|exs| exs.get(idx).map(|e| {
+                            storage::find_alternatives(&e.exercise, &storage::load_data()).into_iter()
+                                .filter(|a| !exs.iter().any(|x| x.exercise.name == a.name))
+                                .take(8)
+                                .collect::<Vec<_>>()
+                        })).unwrap_or_default();
+                        ("Byt övning", alts)
+                    }
+                    ExercisePicker::Add => ("Lägg till övning", vec![]),
+                };
+                view! {
+                    <div class="overview-modal-backdrop" on:click=move |_| set_picker.set(None)>
+                        <div class="overview-modal" on:click=|e| e.stop_propagation()>
+                            <div class="overview-header">
+                                <span class="overview-title">{title}</span>
+                                <button class="overview-close" on:click=move |_| set_picker.set(None)>"✕"</button>
+                            </div>
+                            <div class="picker-body">
+                                {(!alternatives.is_empty()).then(|| view! {
+                                    <div class="picker-section-label">"Samma muskler"</div>
+                                    {alternatives.into_iter().map(|alt| {
+                                        let name = alt.name.clone();
+                                        let muscles = alt.primary_muscles.join(", ");
+                                        view! {
+                                            <button class="picker-item" on:click=move |_| pick_exercise(alt.clone())>
+                                                <span class="picker-item-name">{name}</span>
+                                                <span class="picker-item-muscles">{muscles}</span>
+                                            </button>
+                                        }
+                                    }).collect_view()}
+                                })}
+                                <input
+                                    type="search"
+                                    class="picker-search"
+                                    enterkeyhint="search"
+                                    placeholder="Sök övning (t.ex. leg press)"
+                                    prop:value=picker_query
+                                    on:input=move |e| {
+                                        let val = event_target_value(&e);
+                                        set_picker_query.set(val.clone());
+                                        if val.len() >= 2 {
+                                            picker_debounce.set_value(Some(
+                                                gloo_timers::callback::Timeout::new(400, run_picker_search)
+                                            ));
+                                        } else {
+                                            picker_debounce.set_value(None);
+                                            set_picker_results.set(vec![]);
+                                        }
+                                    }
+                                />
+                                {move || picker_searching.get().then(|| view! {
+                                    <div class="picker-section-label">"Söker..."</div>
+                                })}
+                                {move || picker_results.get().into_iter().map(|ex| {
+                                    let muscles = ex.primary_muscles.join(", ");
+                                    let name = ex.name.clone();
+                                    view! {
+                                        <button class="picker-item" on:click=move |_| {
+                                            pick_exercise(Exercise::from_wger(&ex, 3, "8-12"));
+                                        }>
+                                            <span class="picker-item-name">{name}</span>
+                                            <span class="picker-item-muscles">{muscles}</span>
+                                        </button>
+                                    }
+                                }).collect_view()}
+                            </div>
+                        </div>
+                    </div>
+                }
+            })}
+
             {move || show_cancel_confirm.get().then(|| view! {
                 <div class="modal-overlay">
                     <div class="confirm-dialog">
@@ -723,3 +1747,108 @@ pub fn WorkoutActive(
         </div>
     }
 }
+
+/// Timer choices around the target, in steps that suit its scale.
+/// Last time's duration is always offered.
+fn duration_presets(target: u32, last: Option<u32>) -> Vec<u32> {
+    let step = match target {
+        0..=59 => 5,
+        60..=299 => 15,
+        _ => 60,
+    };
+    let first = target.saturating_sub(3 * step).max(step);
+    let mut presets: Vec<u32> = (0..8).map(|i| first + i * step).collect();
+    if let Some(last) = last.filter(|l| !presets.contains(l)) {
+        presets.push(last);
+        presets.sort_unstable();
+    }
+    presets
+}
+
+/// Exercises grouped the way they're done: a superset, tri-set or circuit is one block
+fn exercise_blocks(exs: &[ExerciseWorkoutState]) -> Vec<std::ops::Range<usize>> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < exs.len() {
+        let key = exs[i].exercise.group_key();
+        let len = match &key {
+            Some(_) => exs[i..].iter().take_while(|e| e.exercise.group_key() == key).count(),
+            None => 1,
+        };
+        blocks.push(i..i + len);
+        i += len;
+    }
+    blocks
+}
+
+/// Every exercise in `idx`'s group, in order; just `idx` when it isn't grouped
+fn group_members(exs: &[ExerciseWorkoutState], idx: usize) -> Vec<usize> {
+    match exs.get(idx).and_then(|e| e.exercise.group_key()) {
+        Some(key) => exs.iter().enumerate()
+            .filter(|(_, e)| e.exercise.group_key().as_ref() == Some(&key))
+            .map(|(i, _)| i)
+            .collect(),
+        None => vec![idx],
+    }
+}
+
+/// Next unfinished member of `idx`'s group, going round from the one after it.
+/// `idx` itself comes last, so an ungrouped exercise stays put until it's done.
+fn next_in_group(exs: &[ExerciseWorkoutState], idx: usize) -> Option<usize> {
+    let members = group_members(exs, idx);
+    let pos = members.iter().position(|&i| i == idx)?;
+    (1..=members.len())
+        .map(|k| members[(pos + k) % members.len()])
+        .find(|&i| !exs[i].is_done())
+}
+
+/// Create or resume the shared AudioContext inside a tap, so iOS lets the rest beep play later
+fn unlock_audio(ctx: StoredValue<Option<web_sys::AudioContext>>) {
+    ctx.update_value(|c| {
+        if c.is_none() {
+            *c = web_sys::AudioContext::new().ok();
+        }
+        if let Some(c) = c {
+            let _ = c.resume();
+        }
+    });
+}
+
+/// Vibrate and beep when the rest countdown reaches zero
+fn rest_done_alert(ctx: StoredValue<Option<web_sys::AudioContext>>) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.navigator().vibrate_with_duration(400);
+    }
+    ctx.with_value(|c| {
+        let Some(c) = c else { return };
+        if let (Ok(osc), Ok(gain)) = (c.create_oscillator(), c.create_gain()) {
+            osc.frequency().set_value(880.0);
+            gain.gain().set_value(0.3);
+            let _ = osc.connect_with_audio_node(&gain);
+            let _ = gain.connect_with_audio_node(&c.destination());
+            let _ = osc.start();
+            let _ = osc.stop_with_when(c.current_time() + 0.4);
+        }
+    });
+}
+
+/// Request a screen wake lock via `navigator.wakeLock`, resolving to its sentinel.
+/// None when the browser lacks the API or refuses (e.g. low battery).
+async fn request_wake_lock() -> Option<wasm_bindgen::JsValue> {
+    use wasm_bindgen::JsCast;
+    let navigator = web_sys::window()?.navigator();
+    let wake_lock = js_sys::Reflect::get(&navigator, &"wakeLock".into()).ok()
+        .filter(|v| !v.is_undefined())?;
+    let request: js_sys::Function = js_sys::Reflect::get(&wake_lock, &"request".into()).ok()?.dyn_into().ok()?;
+    let promise: js_sys::Promise = request.call1(&wake_lock, &"screen".into()).ok()?.dyn_into().ok()?;
+    wasm_bindgen_futures::JsFuture::from(promise).await.ok()
+}
+
+fn release_wake_lock(sentinel: &wasm_bindgen::JsValue) {
+    use wasm_bindgen::JsCast;
+    if let Some(release) = js_sys::Reflect::get(sentinel, &"release".into()).ok()
+        .and_then(|f| f.dyn_into::<js_sys::Function>().ok())
+    {
+        let _ = release.call0(sentinel);
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use crate::types::{PlateSettings, WarmupScheme};
+
+/// Plates on one side of the bar for a given total
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlateLoad {
+    pub total: f64,
+    pub per_side: Vec<f64>,
+}
+
+/// Every per-side load the inventory can build, keyed in grams.
+/// Keeps the combination with the fewest plates for each load.
+fn loadable_per_side(settings: &PlateSettings) -> BTreeMap<i64, Vec<f64>> {
+    let mut sums: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+    sums.insert(0, vec![]);
+
+    let mut plates = settings.plates.clone();
+    plates.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+
+    for stock in plates.iter().filter(|p| p.weight > 0.0) {
+        let current: Vec<(i64, Vec<f64>)> = sums.iter().map(|(k, v)| (*k, v.clone())).collect();
+        for (grams, combo) in current {
+            let mut combo = combo;
+            for n in 1..=stock.pairs as i64 {
+                combo.push(stock.weight);
+                let key = grams + n * (stock.weight * 1000.0).round() as i64;
+                let better = sums.get(&key).map(|c| combo.len() < c.len()).unwrap_or(true);
+                if better {
+                    sums.insert(key, combo.clone());
+                }
+            }
+        }
+    }
+    sums
+}
+
+/// Nearest weight that can be loaded on the bar, with the plates for one side
+pub fn load_for(target: f64, settings: &PlateSettings) -> PlateLoad {
+    let per_side_target = ((target - settings.bar_weight) / 2.0).max(0.0);
+    let target_grams = (per_side_target * 1000.0).round() as i64;
+
+    let sums = loadable_per_side(settings);
+    let (grams, plates) = sums.into_iter()
+        .min_by_key(|(g, _)| (g - target_grams).abs())
+        .unwrap_or((0, vec![]));
+
+    PlateLoad {
+        total: settings.bar_weight + 2.0 * grams as f64 / 1000.0,
+        per_side: plates,
+    }
+}
+
+/// Round a target to the nearest weight the inventory can load
+pub fn round_to_loadable(target: f64, settings: &PlateSettings) -> f64 {
+    load_for(target, settings).total
+}
+
+/// Warm-up sets (weight, reps) leading up to `working_weight`.
+/// Barbell warm-ups round to what the plates can load; anything else rounds to the exercise's `step`.
+/// Steps that round to nothing, the same load or the working weight are dropped.
+pub fn warmup_ramp(working_weight: f64, scheme: WarmupScheme, barbell: bool, step: f64, settings: &PlateSettings) -> Vec<(f64, u8)> {
+    // Auto warm-ups are for the barbell, once the working weight is at least two bars
+    if scheme == WarmupScheme::Auto && (!barbell || working_weight < 2.0 * settings.bar_weight) {
+        return vec![];
+    }
+
+    let mut ramp: Vec<(f64, u8)> = Vec::new();
+    for &(fraction, reps) in scheme.steps() {
+        let weight = if barbell {
+            round_to_loadable(working_weight * fraction, settings)
+        } else {
+            (working_weight * fraction / step).round() * step
+        };
+        if weight <= 0.0 || weight >= working_weight {
+            continue;
+        }
+        if ramp.last().is_some_and(|&(prev, _)| weight <= prev) {
+            continue;
+        }
+        ramp.push((weight, reps));
+    }
+    ramp
+}
@@ -1,23 +1,180 @@
 use std::collections::HashMap;
+use chrono::Datelike;
 use crate::types::*;
 use crate::storage::Database;
 
-/// Brzycki formula for 1RM: weight × (36 / (37 - reps))
-/// More accurate than Epley for rep ranges 1-10
-pub fn calculate_e1rm(weight: f64, reps: u8) -> f64 {
-    if reps == 0 { return 0.0; }
-    if reps == 1 { return weight; }
-    if reps >= 37 { return weight * 2.0; } // Cap it
-    weight * (36.0 / (37.0 - reps as f64))
+/// Reps beyond this say more about endurance than strength, so estimates stop there
+pub const E1RM_MAX_REPS: u8 = 20;
+
+/// %1RM for 1-12 reps taken to failure (RTS chart at RPE 10)
+const RPE_CHART: [f64; 12] = [1.0, 0.955, 0.922, 0.892, 0.863, 0.837, 0.811, 0.786, 0.762, 0.739, 0.707, 0.68];
+
+/// How a one-rep max is estimated from a set
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum E1rmFormula {
+    Epley,
+    #[default]
+    Brzycki,
+    Lombardi,
+    Wathan,
+    /// Reps plus reps in reserve, read off the RPE chart
+    Rpe,
 }
 
-/// Get the best E1RM for an exercise from a session
-pub fn session_best_e1rm(session: &Session, exercise_name: &str) -> Option<f64> {
-    session.exercises.iter()
-        .find(|e| e.name == exercise_name)
+impl E1rmFormula {
+    pub const ALL: [E1rmFormula; 5] = [Self::Epley, Self::Brzycki, Self::Lombardi, Self::Wathan, Self::Rpe];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Epley => "Epley",
+            Self::Brzycki => "Brzycki",
+            Self::Lombardi => "Lombardi",
+            Self::Wathan => "Wathan",
+            Self::Rpe => "RPE",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        Self::ALL.into_iter()
+            .find(|f| f.label().eq_ignore_ascii_case(s))
+            .unwrap_or_default()
+    }
+
+    /// Estimated 1RM from weight × reps. Sets without an RPE count as taken to failure.
+    pub fn estimate(&self, weight: f64, reps: u8, rpe: Option<f64>) -> f64 {
+        if reps == 0 { return 0.0; }
+        if reps == 1 && *self != Self::Rpe { return weight; }
+        let r = reps.min(E1RM_MAX_REPS) as f64;
+        match self {
+            Self::Epley => weight * (1.0 + r / 30.0),
+            Self::Brzycki => weight * 36.0 / (37.0 - r),
+            Self::Lombardi => weight * r.powf(0.1),
+            Self::Wathan => weight * 100.0 / (48.8 + 53.8 * (-0.075 * r).exp()),
+            Self::Rpe => {
+                let total = (r + rpe.map(rpe_to_rir).unwrap_or(0.0)).min(E1RM_MAX_REPS as f64);
+                weight / rpe_chart_pct(total)
+            }
+        }
+    }
+
+    /// Weight that should allow exactly `reps` reps at a given 1RM
+    pub fn weight_for(&self, e1rm: f64, reps: u8) -> f64 {
+        e1rm / self.estimate(1.0, reps.max(1), None)
+    }
+
+    pub fn of_set(&self, set: &SetRecord) -> f64 {
+        self.estimate(set.weight, set.reps, set.rpe)
+    }
+}
+
+/// %1RM for a (possibly fractional) number of reps to failure; past the chart it keeps its last slope
+fn rpe_chart_pct(reps: f64) -> f64 {
+    let last = RPE_CHART.len() - 1;
+    let pos = (reps - 1.0).max(0.0);
+    let i = (pos.floor() as usize).min(last - 1);
+    let t = pos - i as f64;
+    RPE_CHART[i] + (RPE_CHART[i + 1] - RPE_CHART[i]) * t
+}
+
+/// RPE scale offered in the workout picker (10 = max effort)
+pub const RPE_SCALE: [f64; 8] = [6.0, 7.0, 7.5, 8.0, 8.5, 9.0, 9.5, 10.0];
+
+/// RPE that autoregulated suggestions aim for (~2 reps in reserve)
+pub const TARGET_RPE: f64 = 8.0;
+
+/// Reps in reserve implied by an RPE rating
+pub fn rpe_to_rir(rpe: f64) -> f64 {
+    (10.0 - rpe).max(0.0)
+}
+
+/// Suggest next session's weight from an RPE-rated set.
+/// Estimates 1RM from reps + reps in reserve, then solves for the weight that
+/// gives `target_reps` at TARGET_RPE. Capped at ±10% and rounded to `increment`.
+pub fn suggest_weight_from_rpe(weight: f64, reps: u8, rpe: f64, target_reps: u8, increment: f64) -> f64 {
+    if weight <= 0.0 || reps == 0 || increment <= 0.0 {
+        return weight;
+    }
+    let formula = E1rmFormula::Brzycki;
+    let e1rm = formula.estimate(weight, (reps as f64 + rpe_to_rir(rpe)).round() as u8, None);
+    let target_total = (target_reps as f64 + rpe_to_rir(TARGET_RPE)).round() as u8;
+    let suggested = e1rm / formula.estimate(1.0, target_total.max(1), None);
+    let capped = suggested.clamp(weight * 0.9, weight * 1.1);
+    (capped / increment).round() * increment
+}
+
+/// Sessions in a row below the rep range before a deload is prescribed
+pub const STALLS_BEFORE_DELOAD: usize = 2;
+
+/// Share of the load kept after a deload
+pub const DELOAD_FACTOR: f64 = 0.9;
+
+/// Heaviest working-set weight and whether any working set fell below `min_reps`
+fn working_outcome(record: &ExerciseRecord, min_reps: u8) -> Option<(f64, bool)> {
+    let working: Vec<&SetRecord> = record.sets.iter().filter(|s| s.kind.is_working()).collect();
+    if working.is_empty() {
+        return None;
+    }
+    let weight = working.iter().map(|s| s.weight).fold(0.0, f64::max);
+    Some((weight, working.iter().any(|s| s.reps < min_reps)))
+}
+
+/// Double progression: add `increment` once every working set reaches the top of
+/// the rep range, deload after repeated sessions below the bottom of it, else hold.
+/// `history` holds earlier records of the same exercise, newest first.
+pub fn prescribe_progression(record: &ExerciseRecord, history: &[&ExerciseRecord], reps_target: &str, increment: f64, unit: WeightUnit) -> Option<Prescription> {
+    if reps_target.contains("AMRAP") || increment <= 0.0 {
+        return None;
+    }
+    let (min_reps, max_reps) = crate::app::parse_target_range(reps_target);
+    let (weight, missed) = working_outcome(record, min_reps)?;
+    if weight <= 0.0 {
+        return None;
+    }
+
+    let all_at_top = record.sets.iter()
+        .filter(|s| s.kind.is_working())
+        .all(|s| s.reps >= max_reps);
+    if all_at_top {
+        return Some(Prescription {
+            weight: weight + increment,
+            decision: ProgressionDecision::Increase,
+            reason: format!("Alla set på {} reps → +{}", max_reps, crate::app::display_weight(increment, unit)),
+        });
+    }
+
+    if missed {
+        // Count earlier sessions at this weight or heavier that also fell short
+        let stalls = 1 + history.iter()
+            .take_while(|r| matches!(working_outcome(r, min_reps), Some((w, true)) if w >= weight - 0.01))
+            .count();
+        if stalls >= STALLS_BEFORE_DELOAD {
+            let deloaded = ((weight * DELOAD_FACTOR) / increment).round() * increment;
+            return Some(Prescription {
+                weight: deloaded.min(weight - increment).max(0.0),
+                decision: ProgressionDecision::Deload,
+                reason: format!("Under {} reps {} pass i rad → deload", min_reps, stalls),
+            });
+        }
+        return Some(Prescription {
+            weight,
+            decision: ProgressionDecision::Hold,
+            reason: format!("Under {} reps senast – håll vikten", min_reps),
+        });
+    }
+
+    Some(Prescription {
+        weight,
+        decision: ProgressionDecision::Hold,
+        reason: format!("Sikta på {} reps i alla set", max_reps),
+    })
+}
+
+/// Get the best E1RM for an exercise from a session (warm-ups ignored)
+pub fn session_best_e1rm(session: &Session, exercise_id: &str, formula: E1rmFormula) -> Option<f64> {
+    session.exercise(exercise_id)
         .and_then(|e| {
-            e.sets.iter()
-                .map(|s| calculate_e1rm(s.weight, s.reps))
+            e.rep_sets()
+                .map(|s| formula.of_set(s))
                 .fold(None, |max, val| match max {
                     None => Some(val),
                     Some(m) if val > m => Some(val),
@@ -26,20 +183,103 @@ pub fn session_best_e1rm(session: &Session, exercise_name: &str) -> Option<f64>
         })
 }
 
-/// The "Big 4" lifts for power score
-pub const BIG_FOUR: [&str; 4] = ["Squats", "Deadlift", "Bench Press", "Shoulder Press"];
+/// Recent sessions an exercise's current 1RM is judged from, so an old peak doesn't linger
+pub const REP_MAX_SESSIONS: usize = 3;
 
-/// Calculate total power score (sum of E1RM for big 4)
-pub fn calculate_power_score(db: &Database) -> f64 {
-    BIG_FOUR.iter()
-        .map(|&name| {
+/// Best E1RM across the last few sessions with this exercise
+pub fn current_e1rm(db: &Database, exercise_id: &str, formula: E1rmFormula) -> Option<f64> {
+    let mut sessions: Vec<&Session> = db.sessions.iter()
+        .filter(|s| s.exercise(exercise_id).is_some())
+        .collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+    sessions.into_iter()
+        .take(REP_MAX_SESSIONS)
+        .filter_map(|s| session_best_e1rm(s, exercise_id, formula))
+        .filter(|e| *e > 0.0)
+        .reduce(f64::max)
+}
+
+/// Estimated max weight for 1 to 12 reps
+pub fn rep_max_table(e1rm: f64, formula: E1rmFormula) -> Vec<(u8, f64)> {
+    (1..=12).map(|reps| (reps, formula.weight_for(e1rm, reps))).collect()
+}
+
+/// Default lifts for the power score until the user picks their own, by catalog id
+pub const BIG_FOUR: [&str; 4] = ["squat", "deadlift", "bench_press", "shoulder_press"];
+
+/// Calculate total power score (sum of best E1RM for the chosen lifts)
+pub fn calculate_power_score(db: &Database, lifts: &[String], formula: E1rmFormula) -> f64 {
+    lifts.iter()
+        .map(|id| {
             db.sessions.iter()
-                .filter_map(|s| session_best_e1rm(s, name))
+                .filter_map(|s| session_best_e1rm(s, id, formula))
                 .fold(0.0, f64::max)
         })
         .sum()
 }
 
+/// Sessions an exercise's trend is judged over
+pub const TREND_SESSIONS: usize = 6;
+
+/// Weeks without a new best E1RM before an exercise counts as stalled
+pub const STALL_WEEKS: i64 = 3;
+
+/// Sessions in a row, each weaker than the last, that count as a regression
+pub const REGRESSION_SESSIONS: usize = 3;
+
+const WEEK_SECS: i64 = 7 * 86400;
+
+/// How an exercise's recent sessions have stopped moving forward
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlateauKind {
+    /// No new best E1RM in this many weeks
+    Stalled { weeks: i64 },
+    /// Each of the last sessions weaker than the one before, down this much in total
+    Regressing { drop_pct: f64 },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plateau {
+    pub exercise_id: String,
+    pub kind: PlateauKind,
+    /// Latest session with the exercise
+    pub last_timestamp: i64,
+}
+
+/// Look at an exercise's last TREND_SESSIONS sessions for a stall or a steady decline
+pub fn detect_plateau(db: &Database, exercise_id: &str, formula: E1rmFormula) -> Option<Plateau> {
+    let mut points: Vec<(i64, f64)> = db.sessions.iter()
+        .filter_map(|s| session_best_e1rm(s, exercise_id, formula).filter(|e| *e > 0.0).map(|e| (s.timestamp, e)))
+        .collect();
+    points.sort_by_key(|(ts, _)| *ts);
+    let skip = points.len().saturating_sub(TREND_SESSIONS);
+    points.drain(..skip);
+    if points.len() <= REGRESSION_SESSIONS {
+        return None;
+    }
+    let (last_timestamp, latest) = *points.last()?;
+    let plateau = |kind| Some(Plateau { exercise_id: exercise_id.to_string(), kind, last_timestamp });
+
+    // A 1% dip is noise, not a decline
+    let tail = &points[points.len() - REGRESSION_SESSIONS - 1..];
+    if tail.windows(2).all(|w| w[1].1 < w[0].1 * 0.99) {
+        return plateau(PlateauKind::Regressing { drop_pct: (1.0 - latest / tail[0].1) * 100.0 });
+    }
+
+    // Earliest session reaching the window's best; matching it later isn't progress
+    let mut best = 0;
+    for (i, (_, e1rm)) in points.iter().enumerate() {
+        if *e1rm > points[best].1 * 1.005 {
+            best = i;
+        }
+    }
+    let weeks = (last_timestamp - points[best].0) / WEEK_SECS;
+    if points.len() - 1 - best >= REGRESSION_SESSIONS && weeks >= STALL_WEEKS {
+        return plateau(PlateauKind::Stalled { weeks });
+    }
+    None
+}
+
 /// Check if an exercise showed progressive overload vs last time
 #[derive(Clone, Debug, PartialEq)]
 pub enum ProgressStatus {
@@ -49,9 +289,9 @@ pub enum ProgressStatus {
     FirstTime,   // 🆕 No previous data
 }
 
-pub fn check_progressive_overload(db: &Database, exercise_name: &str, current_session: &Session) -> ProgressStatus {
+pub fn check_progressive_overload(db: &Database, exercise_id: &str, current_session: &Session, formula: E1rmFormula) -> ProgressStatus {
     // Find the current exercise data
-    let current = match current_session.exercises.iter().find(|e| e.name == exercise_name) {
+    let current = match current_session.exercise(exercise_id) {
         Some(e) => e,
         None => return ProgressStatus::FirstTime,
     };
@@ -59,28 +299,28 @@ pub fn check_progressive_overload(db: &Database, exercise_name: &str, current_se
     // Find the previous session with this exercise
     let previous_session = db.sessions.iter()
         .filter(|s| s.id != current_session.id && s.timestamp < current_session.timestamp)
-        .filter(|s| s.exercises.iter().any(|e| e.name == exercise_name))
+        .filter(|s| s.exercise(exercise_id).is_some())
         .max_by_key(|s| s.timestamp);
     
     let previous = match previous_session {
-        Some(s) => match s.exercises.iter().find(|e| e.name == exercise_name) {
+        Some(s) => match s.exercise(exercise_id) {
             Some(e) => e,
             None => return ProgressStatus::FirstTime,
         },
         None => return ProgressStatus::FirstTime,
     };
     
-    // Compare E1RM
-    let current_e1rm = current.sets.iter()
-        .map(|s| calculate_e1rm(s.weight, s.reps))
+    // Compare E1RM (warm-ups excluded)
+    let current_e1rm = current.rep_sets()
+        .map(|s| formula.of_set(s))
         .fold(0.0, f64::max);
-    let previous_e1rm = previous.sets.iter()
-        .map(|s| calculate_e1rm(s.weight, s.reps))
+    let previous_e1rm = previous.rep_sets()
+        .map(|s| formula.of_set(s))
         .fold(0.0, f64::max);
     
     // Compare volume
-    let current_volume: f64 = current.sets.iter().map(|s| s.weight * s.reps as f64).sum();
-    let previous_volume: f64 = previous.sets.iter().map(|s| s.weight * s.reps as f64).sum();
+    let current_volume: f64 = current.sets.iter().map(|s| s.volume()).sum();
+    let previous_volume: f64 = previous.sets.iter().map(|s| s.volume()).sum();
     
     // Improved if E1RM or volume increased
     if current_e1rm > previous_e1rm * 1.005 || current_volume > previous_volume * 1.01 {
@@ -131,6 +371,16 @@ impl MuscleGroup {
             MuscleGroup::Core,
         ]
     }
+
+    /// Hours a muscle needs after a typical session: big muscles recover slower
+    pub fn recovery_hours(&self) -> f64 {
+        match self {
+            MuscleGroup::Quads | MuscleGroup::Hamstrings | MuscleGroup::Glutes | MuscleGroup::Back => 72.0,
+            MuscleGroup::Chest | MuscleGroup::Shoulders => 60.0,
+            MuscleGroup::Biceps | MuscleGroup::Triceps => 48.0,
+            MuscleGroup::Calves | MuscleGroup::Core => 36.0,
+        }
+    }
 }
 
 /// Maps muscle names to MuscleGroup.
@@ -172,22 +422,6 @@ pub fn parse_muscle_name(name: &str) -> Option<MuscleGroup> {
     }
 }
 
-/// Get muscle groups for an Exercise struct from its stored muscle data
-pub fn get_muscle_groups_for_exercise(exercise: &crate::types::Exercise) -> Vec<(MuscleGroup, u32)> {
-    let mut result = Vec::new();
-    for name in &exercise.primary_muscles {
-        if let Some(mg) = parse_muscle_name(name) {
-            result.push((mg, 3));
-        }
-    }
-    for name in &exercise.secondary_muscles {
-        if let Some(mg) = parse_muscle_name(name) {
-            result.push((mg, 1));
-        }
-    }
-    result
-}
-
 /// Get muscle groups from a session exercise record
 pub fn muscles_from_record(record: &ExerciseRecord) -> Vec<(MuscleGroup, u32)> {
     let mut result = Vec::new();
@@ -205,8 +439,8 @@ pub fn muscles_from_record(record: &ExerciseRecord) -> Vec<(MuscleGroup, u32)> {
 }
 
 /// Calculate weekly sets per muscle group (primary muscles only).
-/// Counts actual completed sets for each muscle group where the exercise
-/// targets that muscle as primary (weight == 3).
+/// Counts actual completed work sets (no warm-ups) for each muscle group where
+/// the exercise targets that muscle as primary (weight == 3).
 /// Research suggests 10-20 sets per muscle group per week is optimal.
 pub fn calculate_weekly_sets(db: &Database, days: i64) -> HashMap<MuscleGroup, u32> {
     let cutoff = chrono::Utc::now().timestamp() - (days * 86400);
@@ -220,7 +454,7 @@ pub fn calculate_weekly_sets(db: &Database, days: i64) -> HashMap<MuscleGroup, u
         if session.timestamp < cutoff { continue; }
 
         for exercise in &session.exercises {
-            let sets_completed = exercise.sets.len() as u32;
+            let sets_completed = exercise.work_sets().count() as u32;
             if sets_completed == 0 { continue; }
 
             let muscles = muscles_from_record(exercise);
@@ -237,8 +471,249 @@ pub fn calculate_weekly_sets(db: &Database, days: i64) -> HashMap<MuscleGroup, u
     sets
 }
 
+/// Work sets on one muscle that make up a typical session; more stretches recovery, fewer shortens it
+pub const TYPICAL_MUSCLE_SETS: f64 = 6.0;
+
+/// Recency and volume for one muscle group, as drawn on the body map
+#[derive(Clone, Debug)]
+pub struct MuscleFreshness {
+    pub muscle: MuscleGroup,
+    /// Latest session that worked the muscle
+    pub last_trained: Option<i64>,
+    /// Weighted work sets over the last week (secondary muscles count a third)
+    pub weekly_sets: f64,
+    /// 0.0 = just trained, 1.0 = fully recovered
+    pub recovery: f64,
+    /// When the muscle is estimated to be fresh again
+    pub ready_at: Option<i64>,
+}
+
+/// Recovery estimate per muscle group from the latest session that hit it.
+/// The session's weighted sets scale the base recovery time between half and one and a half.
+pub fn muscle_recovery(db: &Database, now: i64) -> Vec<MuscleFreshness> {
+    let week_ago = now - 7 * 86400;
+    let mut last: HashMap<MuscleGroup, (i64, f64)> = HashMap::new();
+    let mut weekly: HashMap<MuscleGroup, f64> = HashMap::new();
+
+    for session in &db.sessions {
+        let mut stimulus: HashMap<MuscleGroup, f64> = HashMap::new();
+        for exercise in &session.exercises {
+            let sets = exercise.work_sets().count() as f64;
+            if sets == 0.0 { continue; }
+            for (muscle, weight) in muscles_from_record(exercise) {
+                *stimulus.entry(muscle).or_insert(0.0) += sets * weight as f64 / 3.0;
+            }
+        }
+        for (muscle, sets) in stimulus {
+            if session.timestamp >= week_ago {
+                *weekly.entry(muscle.clone()).or_insert(0.0) += sets;
+            }
+            let latest = last.entry(muscle).or_insert((session.timestamp, sets));
+            if session.timestamp > latest.0 {
+                *latest = (session.timestamp, sets);
+            }
+        }
+    }
+
+    MuscleGroup::all().into_iter().map(|muscle| {
+        let weekly_sets = weekly.get(&muscle).copied().unwrap_or(0.0);
+        match last.get(&muscle) {
+            Some(&(ts, sets)) => {
+                let hours = muscle.recovery_hours() * (sets / TYPICAL_MUSCLE_SETS).clamp(0.5, 1.5);
+                let elapsed = (now - ts).max(0) as f64 / 3600.0;
+                MuscleFreshness {
+                    muscle,
+                    last_trained: Some(ts),
+                    weekly_sets,
+                    recovery: (elapsed / hours).min(1.0),
+                    ready_at: Some(ts + (hours * 3600.0) as i64),
+                }
+            }
+            None => MuscleFreshness { muscle, last_trained: None, weekly_sets, recovery: 1.0, ready_at: None },
+        }
+    }).collect()
+}
+
+/// Days averaged for the acute (fatigue) and chronic (fitness) load
+pub const ACUTE_DAYS: i64 = 7;
+pub const CHRONIC_DAYS: i64 = 28;
+
+/// Training load of one session: every work set counts 1 plus a point per tonne moved,
+/// scaled by how hard it was rated (RPE 8 = 1.0, unrated sets count as 1.0)
+pub fn session_load(session: &Session) -> f64 {
+    session.exercises.iter()
+        .flat_map(|e| e.work_sets())
+        .map(|s| {
+            let effort = s.rpe.map(|rpe| rpe / TARGET_RPE).unwrap_or(1.0);
+            effort * (1.0 + s.volume() / 1000.0)
+        })
+        .sum()
+}
+
+/// Acute:chronic workload ratio bands
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadZone {
+    /// Under 0.8: doing less than you're used to
+    Low,
+    /// 0.8-1.3: building without outpacing recovery
+    Optimal,
+    /// 1.3-1.5: ramping up quickly
+    Elevated,
+    /// Over 1.5: a spike, where overreaching and injuries cluster
+    Spike,
+}
+
+impl LoadZone {
+    pub const LOW_BELOW: f64 = 0.8;
+    pub const ELEVATED_FROM: f64 = 1.3;
+    pub const SPIKE_FROM: f64 = 1.5;
+
+    pub fn from_ratio(ratio: f64) -> Self {
+        if ratio < Self::LOW_BELOW {
+            LoadZone::Low
+        } else if ratio < Self::ELEVATED_FROM {
+            LoadZone::Optimal
+        } else if ratio < Self::SPIKE_FROM {
+            LoadZone::Elevated
+        } else {
+            LoadZone::Spike
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoadZone::Low => "Låg",
+            LoadZone::Optimal => "Optimal",
+            LoadZone::Elevated => "Förhöjd",
+            LoadZone::Spike => "Topp",
+        }
+    }
+}
+
+/// Acute and chronic load (average per day) as of the end of one day
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadPoint {
+    pub timestamp: i64,
+    pub acute: f64,
+    pub chronic: f64,
+    /// None until there's enough history to compare against
+    pub ratio: Option<f64>,
+}
+
+/// Daily acute:chronic load for the last `days` days, oldest first
+pub fn training_load_history(db: &Database, days: i64) -> Vec<LoadPoint> {
+    let now = chrono::Utc::now().timestamp();
+    let loads: Vec<(i64, f64)> = db.sessions.iter().map(|s| (s.timestamp, session_load(s))).collect();
+    let Some(first) = loads.iter().map(|(ts, _)| *ts).min() else { return vec![] };
+    // Daily average over the window, or over the days logged so far if that is shorter
+    let average = |end: i64, window: i64| {
+        let start = end - window * 86400;
+        let logged_days = ((end - first) as f64 / 86400.0).ceil().max(1.0);
+        loads.iter().filter(|(ts, _)| *ts > start && *ts <= end).map(|(_, l)| l).sum::<f64>()
+            / (window as f64).min(logged_days)
+    };
+
+    (0..days).rev()
+        .map(|back| {
+            let end = now - back * 86400;
+            let acute = average(end, ACUTE_DAYS);
+            let chronic = average(end, CHRONIC_DAYS);
+            // Half a chronic window of history before the ratio means anything
+            let settled = end - first >= CHRONIC_DAYS / 2 * 86400;
+            let ratio = (settled && chronic > 0.0).then(|| acute / chronic);
+            LoadPoint { timestamp: end, acute, chronic, ratio }
+        })
+        .collect()
+}
+
+/// Rest within this many seconds of the target counts as on target
+pub const REST_TOLERANCE_SECS: i64 = 15;
+
+/// How closely rests followed the countdown
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RestAdherence {
+    pub sets: usize,
+    pub over: usize,
+    pub under: usize,
+    pub avg_delta_secs: f64,
+}
+
+/// Compare each set's rest with the target it was shown, over the last `days`
+pub fn calculate_rest_adherence(db: &Database, days: i64) -> RestAdherence {
+    let cutoff = chrono::Utc::now().timestamp() - (days * 86400);
+    let deltas: Vec<i64> = db.sessions.iter()
+        .filter(|s| s.timestamp >= cutoff)
+        .flat_map(|s| &s.exercises)
+        .flat_map(|e| &e.sets)
+        .filter_map(|s| s.rest_vs_target())
+        .collect();
+
+    if deltas.is_empty() {
+        return RestAdherence::default();
+    }
+    RestAdherence {
+        sets: deltas.len(),
+        over: deltas.iter().filter(|&&d| d > REST_TOLERANCE_SECS).count(),
+        under: deltas.iter().filter(|&&d| d < -REST_TOLERANCE_SECS).count(),
+        avg_delta_secs: deltas.iter().sum::<i64>() as f64 / deltas.len() as f64,
+    }
+}
+
+/// Pace history of one cardio exercise, oldest first
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaceTrend {
+    pub exercise_id: String,
+    pub exercise: String,  // Latest name it was logged under
+    pub paces: Vec<(i64, f64)>,  // (timestamp, seconds per km)
+}
+
+/// Distance and time this week plus pace per cardio exercise
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CardioSummary {
+    pub weekly_distance_m: f64,
+    pub weekly_secs: u32,
+    pub trends: Vec<PaceTrend>,
+}
+
+/// Cardio totals over the last `days` and the last 10 paces of each exercise
+pub fn calculate_cardio_summary(db: &Database, days: i64) -> CardioSummary {
+    let cutoff = chrono::Utc::now().timestamp() - (days * 86400);
+    let mut sessions: Vec<_> = db.sessions.iter().collect();
+    sessions.sort_by_key(|s| s.timestamp);
+
+    let mut summary = CardioSummary::default();
+    for session in sessions {
+        for record in session.exercises.iter().filter(|e| e.is_cardio) {
+            if session.timestamp >= cutoff {
+                for set in record.work_sets() {
+                    summary.weekly_distance_m += set.distance_m().unwrap_or(0.0);
+                    summary.weekly_secs += set.duration_secs.unwrap_or(0);
+                }
+            }
+            if let Some(pace) = record.pace_secs_per_km() {
+                match summary.trends.iter_mut().find(|t| t.exercise_id == record.exercise_id) {
+                    Some(trend) => {
+                        trend.exercise = record.name.clone();
+                        trend.paces.push((session.timestamp, pace));
+                    }
+                    None => summary.trends.push(PaceTrend {
+                        exercise_id: record.exercise_id.clone(),
+                        exercise: record.name.clone(),
+                        paces: vec![(session.timestamp, pace)],
+                    }),
+                }
+            }
+        }
+    }
+    for trend in &mut summary.trends {
+        let skip = trend.paces.len().saturating_sub(10);
+        trend.paces.drain(..skip);
+    }
+    summary
+}
+
 /// Power score history (for graphing)
-pub fn get_power_score_history(db: &Database) -> Vec<(i64, f64)> {
+pub fn get_power_score_history(db: &Database, lifts: &[String], formula: E1rmFormula) -> Vec<(i64, f64)> {
     let mut current_best: HashMap<&str, f64> = HashMap::new();
     let mut history: Vec<(i64, f64)> = Vec::new();
 
@@ -246,9 +721,9 @@ pub fn get_power_score_history(db: &Database) -> Vec<(i64, f64)> {
     sessions.sort_by_key(|s| s.timestamp);
 
     for session in sessions {
-        for &lift in &BIG_FOUR {
-            if let Some(e1rm) = session_best_e1rm(session, lift) {
-                let current = current_best.entry(lift).or_insert(0.0);
+        for lift in lifts {
+            if let Some(e1rm) = session_best_e1rm(session, lift, formula) {
+                let current = current_best.entry(lift.as_str()).or_insert(0.0);
                 if e1rm > *current {
                     *current = e1rm;
                 }
@@ -264,6 +739,255 @@ pub fn get_power_score_history(db: &Database) -> Vec<(i64, f64)> {
     history
 }
 
+/// What kind of personal record a set or session beat
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrKind {
+    /// Heaviest weight lifted for exactly this many reps
+    RepMax(u8),
+    E1rm,
+    SetVolume,
+    SessionVolume,
+}
+
+impl PrKind {
+    pub fn label(&self) -> String {
+        match self {
+            PrKind::RepMax(reps) => format!("{}RM", reps),
+            PrKind::E1rm => "E1RM".to_string(),
+            PrKind::SetVolume => "Setvolym".to_string(),
+            PrKind::SessionVolume => "Passvolym".to_string(),
+        }
+    }
+}
+
+/// A best beaten. All values are kg (volume as kg × reps).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersonalRecord {
+    pub exercise_id: String,
+    pub exercise_name: String,
+    pub kind: PrKind,
+    pub value: f64,
+    pub previous: f64,
+    pub timestamp: i64,
+}
+
+/// An exercise's bests so far
+#[derive(Default)]
+struct ExerciseBests {
+    records: HashMap<PrKind, f64>,
+}
+
+impl ExerciseBests {
+    /// Fold one session's record into the bests, returning the ones it beat.
+    /// The first time a kind is seen sets the bar without counting as a PR.
+    fn update(&mut self, record: &ExerciseRecord, formula: E1rmFormula) -> Vec<(PrKind, f64, f64)> {
+        let mut candidates: HashMap<PrKind, f64> = HashMap::new();
+        let mut offer = |kind: PrKind, value: f64| {
+            if value > 0.0 {
+                let best = candidates.entry(kind).or_insert(0.0);
+                *best = best.max(value);
+            }
+        };
+        for set in record.rep_sets() {
+            offer(PrKind::RepMax(set.reps), set.weight);
+            offer(PrKind::E1rm, formula.of_set(set));
+            offer(PrKind::SetVolume, set.volume());
+        }
+        offer(PrKind::SessionVolume, record.sets.iter().map(|s| s.volume()).sum());
+
+        let mut beaten = Vec::new();
+        for (kind, value) in candidates {
+            match self.records.get(&kind).copied() {
+                Some(previous) if value > previous + 0.001 => {
+                    beaten.push((kind, value, previous));
+                    self.records.insert(kind, value);
+                }
+                Some(_) => {}
+                None => { self.records.insert(kind, value); }
+            }
+        }
+        beaten.sort_by_key(|(kind, _, _)| pr_order(*kind));
+        beaten
+    }
+}
+
+/// E1RM first, then rep maxes from heaviest, then volume
+fn pr_order(kind: PrKind) -> (u8, u8) {
+    match kind {
+        PrKind::E1rm => (0, 0),
+        PrKind::RepMax(reps) => (1, reps),
+        PrKind::SetVolume => (2, 0),
+        PrKind::SessionVolume => (3, 0),
+    }
+}
+
+/// Records a new session's exercises beat, compared with everything already in the database
+pub fn detect_prs(db: &Database, exercises: &[ExerciseRecord], timestamp: i64, formula: E1rmFormula) -> Vec<PersonalRecord> {
+    let mut prs = Vec::new();
+    for record in exercises.iter().filter(|e| !e.is_cardio) {
+        let mut bests = ExerciseBests::default();
+        for earlier in db.sessions.iter().filter_map(|s| s.exercise(&record.exercise_id)) {
+            bests.update(earlier, formula);
+        }
+        prs.extend(bests.update(record, formula).into_iter().map(|(kind, value, previous)| PersonalRecord {
+            exercise_id: record.exercise_id.clone(),
+            exercise_name: record.name.clone(),
+            kind,
+            value,
+            previous,
+            timestamp,
+        }));
+    }
+    prs
+}
+
+/// Every record an exercise has set, oldest first
+pub fn pr_timeline(db: &Database, exercise_id: &str, formula: E1rmFormula) -> Vec<PersonalRecord> {
+    let mut sessions: Vec<&Session> = db.sessions.iter()
+        .filter(|s| s.exercise(exercise_id).is_some())
+        .collect();
+    sessions.sort_by_key(|s| s.timestamp);
+
+    let mut bests = ExerciseBests::default();
+    let mut timeline = Vec::new();
+    for session in sessions {
+        let Some(record) = session.exercise(exercise_id) else { continue };
+        timeline.extend(bests.update(record, formula).into_iter().map(|(kind, value, previous)| PersonalRecord {
+            exercise_id: exercise_id.to_string(),
+            exercise_name: record.name.clone(),
+            kind,
+            value,
+            previous,
+            timestamp: session.timestamp,
+        }));
+    }
+    timeline
+}
+
+/// Heaviest weight at each rep count ever logged, with when it was lifted
+pub fn rep_bests(db: &Database, exercise_id: &str) -> Vec<(u8, f64, i64)> {
+    let mut bests: HashMap<u8, (f64, i64)> = HashMap::new();
+    for session in &db.sessions {
+        let Some(record) = session.exercise(exercise_id) else { continue };
+        for set in record.rep_sets().filter(|s| s.weight > 0.0) {
+            let best = bests.entry(set.reps).or_insert((0.0, session.timestamp));
+            if set.weight > best.0 {
+                *best = (set.weight, session.timestamp);
+            }
+        }
+    }
+    let mut rows: Vec<_> = bests.into_iter().map(|(reps, (w, ts))| (reps, w, ts)).collect();
+    rows.sort_by_key(|(reps, _, _)| *reps);
+    rows
+}
+
+/// The three powerlifts, by catalog id, for the relative strength scores
+pub const POWERLIFTS: [&str; 3] = ["squat", "bench_press", "deadlift"];
+
+/// McCulloch age coefficients for lifters aged 40-80
+const MCCULLOCH: [f64; 41] = [
+    1.000, 1.010, 1.020, 1.031, 1.043, 1.055, 1.068, 1.082, 1.097, 1.113,
+    1.130, 1.147, 1.165, 1.184, 1.204, 1.225, 1.246, 1.268, 1.291, 1.315,
+    1.340, 1.366, 1.393, 1.421, 1.450, 1.480, 1.511, 1.543, 1.576, 1.610,
+    1.645, 1.681, 1.718, 1.756, 1.795, 1.835, 1.876, 1.918, 1.961, 2.005,
+    2.050,
+];
+
+/// Score multiplier for masters lifters; 1.0 below 40
+pub fn age_coefficient(age: i32) -> f64 {
+    MCCULLOCH[(age.clamp(40, 80) - 40) as usize]
+}
+
+/// Wilks points (original coefficients), total and bodyweight in kg
+pub fn wilks(total: f64, bodyweight: f64, sex: Sex) -> f64 {
+    let (c, x) = match sex {
+        Sex::Male => ([-216.0475144, 16.2606339, -0.002388645, -0.00113732, 7.01863e-06, -1.291e-08], bodyweight.clamp(40.0, 201.9)),
+        Sex::Female => ([594.31747775582, -27.23842536447, 0.82112226871, -0.00930733913, 4.731582e-05, -9.054e-08], bodyweight.clamp(26.51, 154.53)),
+    };
+    total * 500.0 / polynomial(&c, x)
+}
+
+/// DOTS points, total and bodyweight in kg
+pub fn dots(total: f64, bodyweight: f64, sex: Sex) -> f64 {
+    let (c, x) = match sex {
+        Sex::Male => ([-307.75076, 24.0900756, -0.1918759221, 0.0007391293, -0.000001093], bodyweight.clamp(40.0, 210.0)),
+        Sex::Female => ([-57.96288, 13.6175032, -0.1126655495, 0.0005158568, -0.0000010706], bodyweight.clamp(40.0, 150.0)),
+    };
+    total * 500.0 / polynomial(&c, x)
+}
+
+/// IPF GL points for classic (raw) powerlifting, total and bodyweight in kg
+pub fn ipf_gl(total: f64, bodyweight: f64, sex: Sex) -> f64 {
+    let (a, b, c) = match sex {
+        Sex::Male => (1199.72839, 1025.18162, 0.00921),
+        Sex::Female => (610.32796, 1045.59282, 0.03048),
+    };
+    if bodyweight < 35.0 { return 0.0; }
+    total * 100.0 / (a - b * (-c * bodyweight).exp())
+}
+
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+/// Relative strength after one session, from the best estimated powerlift total so far
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelativeScore {
+    pub timestamp: i64,
+    pub total: f64,
+    pub bodyweight: f64,
+    /// Wilks with the McCulloch masters coefficient; DOTS and IPF GL have no age term
+    pub wilks: f64,
+    pub dots: f64,
+    pub ipf_gl: f64,
+    /// McCulloch coefficient applied to `wilks` (1.0 below 40 or without a birth year)
+    pub age_factor: f64,
+}
+
+/// DOTS, Wilks and IPF GL over time, once all three powerlifts have been logged.
+/// Each session is scored at the bodyweight logged closest to it.
+pub fn relative_strength_history(db: &Database, profile: &AthleteProfile, formula: E1rmFormula) -> Vec<RelativeScore> {
+    let Some(sex) = profile.sex else { return vec![] };
+    let mut best: HashMap<&str, f64> = HashMap::new();
+    let mut history = Vec::new();
+
+    let mut sessions: Vec<_> = db.sessions.iter().collect();
+    sessions.sort_by_key(|s| s.timestamp);
+
+    for session in sessions {
+        let mut logged = false;
+        for lift in POWERLIFTS {
+            if let Some(e1rm) = session_best_e1rm(session, lift, formula) {
+                let current = best.entry(lift).or_insert(0.0);
+                if e1rm > *current {
+                    *current = e1rm;
+                }
+                logged = true;
+            }
+        }
+        if !logged || best.len() < POWERLIFTS.len() {
+            continue;
+        }
+        let Some(bodyweight) = db.bodyweight_at(session.timestamp) else { continue };
+        let total: f64 = best.values().sum();
+        let age_factor = profile.birth_year
+            .and_then(|y| chrono::DateTime::from_timestamp(session.timestamp, 0).map(|d| d.year() - y))
+            .map(age_coefficient)
+            .unwrap_or(1.0);
+        history.push(RelativeScore {
+            timestamp: session.timestamp,
+            total,
+            bodyweight,
+            wilks: wilks(total, bodyweight, sex) * age_factor,
+            dots: dots(total, bodyweight, sex),
+            ipf_gl: ipf_gl(total, bodyweight, sex),
+            age_factor,
+        });
+    }
+
+    history
+}
+
 /// Comprehensive stats summary
 #[derive(Clone, Debug, PartialEq)]
 pub struct StatsSummary {
@@ -271,18 +995,20 @@ pub struct StatsSummary {
     pub bodyweight: f64,
     pub total_sessions: usize,
     pub weekly_sets: HashMap<MuscleGroup, u32>,
-    pub e1rm_by_exercise: HashMap<String, f64>,
+    pub e1rm_by_exercise: HashMap<String, f64>,  // Keyed by exercise id
+    pub rest_adherence: RestAdherence,
+    pub cardio: CardioSummary,
 }
 
-pub fn get_stats_summary(db: &Database, bodyweight: f64) -> StatsSummary {
-    let power_score = calculate_power_score(db);
+pub fn get_stats_summary(db: &Database, bodyweight: f64, lifts: &[String], formula: E1rmFormula) -> StatsSummary {
+    let power_score = calculate_power_score(db, lifts, formula);
 
     // Get best E1RM for each exercise
     let mut e1rm_by_exercise: HashMap<String, f64> = HashMap::new();
     for session in &db.sessions {
         for exercise in &session.exercises {
-            let best = session_best_e1rm(session, &exercise.name).unwrap_or(0.0);
-            let current = e1rm_by_exercise.entry(exercise.name.clone()).or_insert(0.0);
+            let best = session_best_e1rm(session, &exercise.exercise_id, formula).unwrap_or(0.0);
+            let current = e1rm_by_exercise.entry(exercise.exercise_id.clone()).or_insert(0.0);
             if best > *current {
                 *current = best;
             }
@@ -295,6 +1021,8 @@ pub fn get_stats_summary(db: &Database, bodyweight: f64) -> StatsSummary {
         total_sessions: db.sessions.len(),
         weekly_sets: calculate_weekly_sets(db, 7),
         e1rm_by_exercise,
+        rest_adherence: calculate_rest_adherence(db, 28),
+        cardio: calculate_cardio_summary(db, 7),
     }
 }
 
@@ -6,6 +6,10 @@ const SYNC_STATUS_KEY: &str = "oxidize_sync_status";
 const DATA_VERSION_KEY: &str = "oxidize_data_version";
 const ACTIVE_ROUTINE_KEY: &str = "oxidize_active_routine";
 const DISPLAY_NAME_KEY: &str = "oxidize_display_name";
+const WEIGHT_UNIT_KEY: &str = "oxidize_weight_unit";
+const BIG_LIFTS_KEY: &str = "oxidize_big_lifts";
+const E1RM_FORMULA_KEY: &str = "oxidize_e1rm_formula";
+const PROFILE_KEY: &str = "oxidize_profile";
 
 // Sync status: "pending", "success", "failed"
 pub fn get_sync_status() -> &'static str {
@@ -62,6 +66,67 @@ pub fn save_display_name(name: &str) {
     }
 }
 
+pub fn load_weight_unit() -> WeightUnit {
+    get_local_storage()
+        .and_then(|s| s.get_item(WEIGHT_UNIT_KEY).ok())
+        .flatten()
+        .map(|v| WeightUnit::parse(&v))
+        .unwrap_or_default()
+}
+
+pub fn save_weight_unit(unit: WeightUnit) {
+    if let Some(storage) = get_local_storage() {
+        let _ = storage.set_item(WEIGHT_UNIT_KEY, unit.label());
+    }
+}
+
+/// Exercise ids that make up the strength total
+pub fn load_big_lifts() -> Vec<String> {
+    get_local_storage()
+        .and_then(|s| s.get_item(BIG_LIFTS_KEY).ok())
+        .flatten()
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_else(|| crate::stats::BIG_FOUR.iter().map(|id| id.to_string()).collect())
+}
+
+pub fn save_big_lifts(lifts: &[String]) {
+    if let Some(storage) = get_local_storage() {
+        if let Ok(json) = serde_json::to_string(lifts) {
+            let _ = storage.set_item(BIG_LIFTS_KEY, &json);
+        }
+    }
+}
+
+pub fn load_e1rm_formula() -> crate::stats::E1rmFormula {
+    get_local_storage()
+        .and_then(|s| s.get_item(E1RM_FORMULA_KEY).ok())
+        .flatten()
+        .map(|v| crate::stats::E1rmFormula::parse(&v))
+        .unwrap_or_default()
+}
+
+pub fn save_e1rm_formula(formula: crate::stats::E1rmFormula) {
+    if let Some(storage) = get_local_storage() {
+        let _ = storage.set_item(E1RM_FORMULA_KEY, formula.label());
+    }
+}
+
+pub fn load_profile() -> AthleteProfile {
+    get_local_storage()
+        .and_then(|s| s.get_item(PROFILE_KEY).ok())
+        .flatten()
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_profile(profile: &AthleteProfile) {
+    if let Some(storage) = get_local_storage() {
+        if let Ok(json) = serde_json::to_string(profile) {
+            let _ = storage.set_item(PROFILE_KEY, &json);
+        }
+    }
+}
+
 pub fn mark_sync_failed() {
     if let Some(storage) = get_local_storage() {
         let _ = storage.set_item(SYNC_STATUS_KEY, "failed");
@@ -76,6 +141,10 @@ pub fn reset_sync_status() {
 
 // Active routine storage
 pub fn save_active_routine(routine: &SavedRoutine) {
+    let mut db = load_data();
+    if db.learn_aliases(routine) {
+        let _ = save_data(&db);
+    }
     if let Some(storage) = get_local_storage() {
         if let Ok(json) = serde_json::to_string(routine) {
             let _ = storage.set_item(ACTIVE_ROUTINE_KEY, &json);
@@ -119,7 +188,12 @@ pub fn load_data() -> Database {
         _ => return Database::default(),
     };
 
-    serde_json::from_str(&json).unwrap_or_default()
+    let mut db: Database = serde_json::from_str(&json).unwrap_or_default();
+    // One-time upgrade of data saved before exercise ids
+    if db.migrate_exercise_ids() {
+        let _ = save_data(&db);
+    }
+    db
 }
 
 // Paused workout functions
@@ -136,6 +210,17 @@ pub fn load_paused_workout() -> Option<PausedWorkout> {
     serde_json::from_str(&json).ok()
 }
 
+/// Back from the exercise history: the time away counts as workout time on resume.
+/// Any other way back into the workout resumes it like a normal pause.
+pub fn return_from_history() {
+    if let Some(mut paused) = load_paused_workout() {
+        if paused.left_at.is_some() {
+            paused.clock_running = true;
+            let _ = save_paused_workout(&paused);
+        }
+    }
+}
+
 pub fn clear_paused_workout() {
     if let Some(storage) = get_local_storage() {
         let _ = storage.remove_item(PAUSED_WORKOUT_KEY);
@@ -156,8 +241,34 @@ pub struct Database {
     pub bodyweight: Option<f64>,
     #[serde(default)]
     pub bodyweight_history: Vec<BodyweightEntry>,
+    #[serde(default)]
+    pub plate_settings: PlateSettings,
+    /// Next-session weights from the progression engine, keyed by exercise id
+    #[serde(default)]
+    pub prescriptions: std::collections::HashMap<String, Prescription>,
+    /// Session ids edited locally whose new version hasn't reached Supabase yet
+    #[serde(default)]
+    pub pending_session_edits: Vec<String>,
+    /// Session ids deleted locally whose deletion hasn't reached Supabase yet
+    #[serde(default)]
+    pub pending_session_deletes: Vec<String>,
+    /// Exercise names whose last weight was removed locally but not yet from Supabase
+    #[serde(default)]
+    pub pending_weight_deletes: Vec<String>,
+    /// The user's own names for exercises (normalized name → id), e.g. after a rename
+    #[serde(default)]
+    pub exercise_aliases: std::collections::HashMap<String, String>,
+    /// Plateau warnings waved off: exercise id → its latest session when dismissed
+    #[serde(default)]
+    pub dismissed_plateaus: std::collections::HashMap<String, i64>,
+    /// One-time upgrades already run on this data, see `Database::migrate_exercise_ids`
+    #[serde(default)]
+    pub migration_version: u32,
 }
 
+/// Bumped when `Database::migrate_exercise_ids` gains a one-time step
+const MIGRATION_VERSION: u32 = 1;
+
 impl Database {
     pub fn set_bodyweight(&mut self, weight: f64) {
         self.bodyweight = Some(weight);
@@ -170,13 +281,107 @@ impl Database {
     pub fn get_bodyweight(&self) -> Option<f64> {
         self.bodyweight
     }
+
+    /// Logged bodyweight closest in time to `timestamp`, else the current one
+    pub fn bodyweight_at(&self, timestamp: i64) -> Option<f64> {
+        self.bodyweight_history.iter()
+            .min_by_key(|e| (e.timestamp - timestamp).abs())
+            .map(|e| e.weight)
+            .or(self.bodyweight)
+    }
+
+    /// Stable id for an exercise name
+    pub fn exercise_id(&self, name: &str) -> String {
+        crate::catalog::exercise_id(name, &self.exercise_aliases)
+    }
+
+    /// Stable id for a routine exercise; one that was renamed carries its id along
+    pub fn id_of(&self, ex: &Exercise) -> String {
+        ex.id.clone().unwrap_or_else(|| self.exercise_id(&ex.name))
+    }
+
+    /// Name to show for an exercise id: what it was last logged as, else the catalog name
+    pub fn exercise_name(&self, id: &str) -> String {
+        self.sessions.iter()
+            .filter_map(|s| s.exercise(id).map(|e| (s.timestamp, &e.name)))
+            .max_by_key(|(ts, _)| *ts)
+            .map(|(_, name)| name.clone())
+            .or_else(|| crate::catalog::display_name(id).map(str::to_string))
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    /// Remember that `name` is exercise `id`. Returns true if that was news.
+    /// A name the catalog already gives another exercise is refused, so renaming
+    /// Squats to "Bench Press" can't turn every bench press into a squat.
+    pub fn add_alias(&mut self, name: &str, id: &str) -> bool {
+        let key = crate::catalog::normalize(name);
+        if key.is_empty() || self.exercise_aliases.get(&key).map(String::as_str) == Some(id) {
+            return false;
+        }
+        if crate::catalog::lookup(name).is_some_and(|e| e.id != id) {
+            return false;
+        }
+        self.exercise_aliases.insert(key, id.to_string());
+        true
+    }
+
+    /// Learn the names a routine uses for exercises with a fixed id,
+    /// so a rename made on another device resolves here too
+    pub fn learn_aliases(&mut self, routine: &SavedRoutine) -> bool {
+        let mut changed = false;
+        for ex in routine.passes.iter().flat_map(|p| p.exercises.iter().chain(p.finishers.iter())) {
+            if let Some(id) = &ex.id {
+                changed |= self.add_alias(&ex.name, id);
+            }
+        }
+        changed
+    }
+
+    /// Give records from before exercise ids an id from their name, and (once per
+    /// database) move prescriptions from name keys to id keys. Returns true if anything changed.
+    pub fn migrate_exercise_ids(&mut self) -> bool {
+        let aliases = &self.exercise_aliases;
+        let mut changed = false;
+        for record in self.sessions.iter_mut().flat_map(|s| s.exercises.iter_mut()) {
+            if record.exercise_id.is_empty() {
+                record.exercise_id = crate::catalog::exercise_id(&record.name, aliases);
+                changed = true;
+            }
+        }
+        if self.migration_version >= MIGRATION_VERSION {
+            return changed;
+        }
+
+        let known: std::collections::HashSet<String> = self.sessions.iter()
+            .flat_map(|s| s.exercises.iter().map(|e| e.exercise_id.clone()))
+            .chain(crate::catalog::CATALOG.iter().map(|e| e.id.to_string()))
+            .collect();
+        let stale: Vec<String> = self.prescriptions.keys()
+            .filter(|k| !known.contains(*k))
+            .cloned()
+            .collect();
+        for key in stale {
+            // Earlier builds dropped the underscores from ids ("bench_press" → "benchpress")
+            let id = known.iter()
+                .find(|id| id.replace('_', "") == key)
+                .cloned()
+                .unwrap_or_else(|| self.exercise_id(&key));
+            if id != key {
+                if let Some(p) = self.prescriptions.remove(&key) {
+                    self.prescriptions.entry(id).or_insert(p);
+                }
+            }
+        }
+        self.migration_version = MIGRATION_VERSION;
+        true
+    }
 }
 
 impl Database {
     pub fn add_session(&mut self, session: Session) {
         // Update last weights for each exercise
         for ex in &session.exercises {
-            if let Some(last_set) = ex.sets.last() {
+            if let Some(last_set) = ex.last_working_set() {
                 self.last_weights.insert(
                     ex.name.clone(),
                     LastExerciseData {
@@ -189,13 +394,56 @@ impl Database {
         self.sessions.push(session);
     }
 
+    /// Last weight × reps by exact name, else from the latest session under any name
     pub fn get_last_exercise_data(&self, exercise: &str) -> Option<LastExerciseData> {
-        self.last_weights.get(exercise).cloned()
+        self.last_weights.get(exercise).cloned().or_else(|| {
+            self.latest_working_set(exercise).map(|set| LastExerciseData { weight: set.weight, reps: set.reps })
+        })
+    }
+
+    /// Last RPE-rated set of an exercise from the most recent session it was done in
+    pub fn get_last_rated_set(&self, exercise: &str) -> Option<SetRecord> {
+        let id = self.exercise_id(exercise);
+        self.sessions.iter()
+            .filter_map(|s| s.exercise(&id).map(|e| (s.timestamp, e)))
+            .max_by_key(|(ts, _)| *ts)
+            .and_then(|(_, e)| e.sets.iter().rev().find(|s| s.kind.is_working() && s.rpe.is_some()).cloned())
+    }
+
+    /// Last working set of an exercise from the most recent session that has one
+    fn latest_working_set(&self, exercise: &str) -> Option<SetRecord> {
+        let id = self.exercise_id(exercise);
+        self.sessions.iter()
+            .filter_map(|s| s.exercise(&id)
+                .and_then(|e| e.last_working_set())
+                .map(|set| (s.timestamp, set)))
+            .max_by_key(|(ts, _)| *ts)
+            .map(|(_, set)| set.clone())
+    }
+
+    /// The exercise's record from the most recent session it was done in
+    pub fn last_exercise_record(&self, exercise: &str) -> Option<&ExerciseRecord> {
+        let id = self.exercise_id(exercise);
+        self.sessions.iter()
+            .filter_map(|s| s.exercise(&id).map(|e| (s.timestamp, e)))
+            .max_by_key(|(ts, _)| *ts)
+            .map(|(_, e)| e)
+    }
+
+    /// Duration of the last timed set of an exercise
+    pub fn last_duration(&self, exercise: &str) -> Option<u32> {
+        let id = self.exercise_id(exercise);
+        self.sessions.iter()
+            .filter_map(|s| s.exercise(&id)
+                .and_then(|e| e.sets.iter().rev().find_map(|set| set.duration_secs))
+                .map(|d| (s.timestamp, d)))
+            .max_by_key(|(ts, _)| *ts)
+            .map(|(_, d)| d)
     }
 
     pub fn get_recent_sessions(&self, limit: usize) -> Vec<Session> {
         let mut sessions = self.sessions.clone();
-        sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
         sessions.into_iter().take(limit).collect()
     }
 
@@ -204,7 +452,7 @@ impl Database {
         let total_volume: f64 = self.sessions.iter().map(|s| s.total_volume).sum();
         let total_sets: usize = self.sessions.iter()
             .flat_map(|s| &s.exercises)
-            .map(|e| e.sets.len())
+            .map(|e| e.work_sets().count())
             .sum();
         
         let avg_duration = if total_sessions > 0 {
@@ -281,7 +529,8 @@ pub fn get_workout(pass_name: &str) -> Option<WorkoutData> {
     // Use active routine from Supabase, fallback to defaults
     let saved_routine = load_active_routine().unwrap_or_else(create_default_routine);
 
-    let pass = saved_routine.passes.iter().find(|p| p.name == pass_name)?;
+    let mut pass = saved_routine.passes.iter().find(|p| p.name == pass_name)?.clone();
+    pass.normalize_groups();
     let routine = Routine {
         name: pass.name.clone(),
         focus: saved_routine.focus.clone(),
@@ -302,47 +551,218 @@ fn create_workout_data(routine: Routine, db: &Database) -> WorkoutData {
 
     let exercises: Vec<ExerciseWorkoutState> = all_exercises
         .iter()
-        .map(|ex| {
-            let last_data = db.get_last_exercise_data(&ex.name);
-            // Bodyweight exercises default to 0 weight
-            let current_weight = if ex.is_bodyweight {
-                0.0
-            } else {
-                last_data.as_ref().map(|d| d.weight).unwrap_or(20.0)
-            };
-            ExerciseWorkoutState {
-                exercise: (*ex).clone(),
-                last_data,
-                current_weight,
-                sets_completed: Vec::new(),
+        .map(|ex| workout_state_for(ex, db))
+        .collect();
+
+    WorkoutData { routine, exercises }
+}
+
+/// Fresh workout state for one exercise, with its starting weight
+pub fn workout_state_for(ex: &Exercise, db: &Database) -> ExerciseWorkoutState {
+    let last_data = db.get_last_exercise_data(&ex.name);
+    // Bodyweight and cardio exercises default to 0 weight
+    let (current_weight, weight_note) = if ex.is_bodyweight || ex.is_cardio {
+        (0.0, None)
+    } else if let Some(deload) = ex.deload_weight {
+        // A deload planned on the routine beats the progression engine
+        (deload, Some("Platå → deload".to_string()))
+    } else {
+        let last_weight = last_data.as_ref().map(|d| d.weight).unwrap_or(db.plate_settings.bar_weight);
+        let prescription = db.prescriptions.get(&db.id_of(ex));
+        match prescription {
+            Some(p) if p.decision != ProgressionDecision::Hold => (p.weight, Some(p.reason.clone())),
+            // While holding, RPE may still nudge the load within the range
+            _ => {
+                let (weight, note) = suggest_from_last_rpe(ex, db).unwrap_or((last_weight, None));
+                (weight, note.or_else(|| prescription.map(|p| p.reason.clone())))
             }
+        }
+    };
+    ExerciseWorkoutState {
+        exercise: ex.clone(),
+        last_data,
+        current_weight,
+        sets_completed: Vec::new(),
+        weight_note,
+    }
+}
+
+/// Known exercises that share a primary muscle with `ex`, most overlap first.
+/// Drawn from the active routine and the session history.
+pub fn find_alternatives(ex: &Exercise, db: &Database) -> Vec<Exercise> {
+    let muscles: Vec<String> = ex.primary_muscles.iter().map(|m| m.to_lowercase()).collect();
+    if muscles.is_empty() {
+        return vec![];
+    }
+
+    let routine = load_active_routine().unwrap_or_else(create_default_routine);
+    let mut known: Vec<Exercise> = routine.passes.iter()
+        .flat_map(|p| p.exercises.iter().chain(p.finishers.iter()))
+        .cloned()
+        .collect();
+    for record in db.sessions.iter().flat_map(|s| &s.exercises) {
+        if !known.iter().any(|k| db.id_of(k) == record.exercise_id) {
+            let mut e = Exercise::standard(&record.name, ex.sets, &ex.reps_target);
+            e.id = Some(record.exercise_id.clone());
+            e.primary_muscles = record.primary_muscles.clone();
+            e.secondary_muscles = record.secondary_muscles.clone();
+            known.push(e);
+        }
+    }
+
+    // One entry per exercise, however many passes it appears in
+    let mut seen = std::collections::HashSet::from([db.id_of(ex)]);
+    let mut scored: Vec<(usize, Exercise)> = known.into_iter()
+        .filter(|k| seen.insert(db.id_of(k)))
+        .map(|k| {
+            let overlap = k.primary_muscles.iter().filter(|m| muscles.contains(&m.to_lowercase())).count();
+            (overlap, k)
         })
+        .filter(|(overlap, _)| *overlap > 0)
         .collect();
+    scored.sort_by_key(|(overlap, _)| std::cmp::Reverse(*overlap));
+    scored.into_iter().map(|(_, k)| k).collect()
+}
 
-    WorkoutData { routine, exercises }
+/// Autoregulated starting weight based on how hard the last session's set felt
+fn suggest_from_last_rpe(ex: &Exercise, db: &Database) -> Option<(f64, Option<String>)> {
+    if ex.duration_secs.is_some() {
+        return None;
+    }
+    let set = db.get_last_rated_set(&ex.name)?;
+    let rpe = set.rpe?;
+    let target_reps = crate::app::parse_target_reps(&ex.reps_target);
+    let unit = load_weight_unit();
+    let weight = ex.clamp_weight(crate::stats::suggest_weight_from_rpe(set.weight, set.reps, rpe, target_reps, ex.weight_step(unit)));
+    let note = (weight != set.weight).then(|| format!(
+        "RPE {} senast ({} × {})",
+        crate::app::format_weight(rpe), crate::app::display_weight(set.weight, unit), set.reps
+    ));
+    Some((weight, note))
+}
+
+/// Stalled or regressing exercises in a routine that still need a decision.
+/// A planned deload or a dismissal quiets one until it's trained again.
+pub fn routine_plateaus(routine: &SavedRoutine, db: &Database) -> Vec<(Exercise, crate::stats::Plateau)> {
+    let formula = load_e1rm_formula();
+    let mut seen: Vec<String> = Vec::new();
+    let mut found = Vec::new();
+    for ex in routine.passes.iter().flat_map(|p| p.exercises.iter().chain(p.finishers.iter())) {
+        let id = db.id_of(ex);
+        if ex.is_bodyweight || ex.is_cardio || ex.duration_secs.is_some() || seen.contains(&id) {
+            continue;
+        }
+        seen.push(id.clone());
+        if ex.deload_weight.is_some() || db.prescriptions.get(&id).is_some_and(|p| p.decision == ProgressionDecision::Deload) {
+            continue;
+        }
+        if let Some(plateau) = crate::stats::detect_plateau(db, &id, formula) {
+            if db.dismissed_plateaus.get(&id).is_some_and(|ts| *ts >= plateau.last_timestamp) {
+                continue;
+            }
+            found.push((ex.clone(), plateau));
+        }
+    }
+    found
+}
+
+/// Plan a deload for an exercise's next session in the active routine: last top weight × DELOAD_FACTOR.
+/// Returns the updated routine so the caller can push it to the cloud.
+pub fn apply_deload(ex: &Exercise) -> Option<SavedRoutine> {
+    let mut routine = load_active_routine()?;
+    let db = load_data();
+    let id = db.id_of(ex);
+    let weight = db.last_exercise_record(&ex.name).and_then(|r| r.last_working_set()).map(|s| s.weight)?;
+    let unit = load_weight_unit();
+    let increment = ex.weight_step(unit);
+    let deloaded = ex.clamp_weight(((weight * crate::stats::DELOAD_FACTOR) / increment).round() * increment);
+    let mut planned = false;
+    for slot in routine.passes.iter_mut().flat_map(|p| p.exercises.iter_mut().chain(p.finishers.iter_mut())) {
+        if db.id_of(slot) == id {
+            slot.deload_weight = Some(deloaded);
+            planned = true;
+        }
+    }
+    if !planned {
+        return None;
+    }
+    save_active_routine(&routine);
+    Some(routine)
+}
+
+/// Drop planned deloads for exercises a finished session trained.
+/// Returns the updated routine if anything was cleared.
+fn clear_deloads(db: &Database, session: &Session) -> Option<SavedRoutine> {
+    let mut routine = load_active_routine()?;
+    let mut cleared = false;
+    for slot in routine.passes.iter_mut().flat_map(|p| p.exercises.iter_mut().chain(p.finishers.iter_mut())) {
+        if slot.deload_weight.is_some() && session.exercise(&db.id_of(slot)).is_some() {
+            slot.deload_weight = None;
+            cleared = true;
+        }
+    }
+    if !cleared {
+        return None;
+    }
+    save_active_routine(&routine);
+    Some(routine)
 }
 
-pub fn save_session(routine_name: String, exercises: Vec<ExerciseRecord>, duration_secs: i64) {
+pub fn dismiss_plateau(plateau: &crate::stats::Plateau) {
     let mut db = load_data();
+    db.dismissed_plateaus.insert(plateau.exercise_id.clone(), plateau.last_timestamp);
+    let _ = save_data(&db);
+}
+
+/// Put `replacement` in every slot of the active routine that holds `old`.
+/// Returns the updated routine so the caller can push it to the cloud.
+pub fn swap_in_active_routine(old: &Exercise, replacement: &Exercise) -> Option<SavedRoutine> {
+    let mut routine = load_active_routine()?;
+    let db = load_data();
+    let old_id = db.id_of(old);
+    let mut swapped = false;
+    for slot in routine.passes.iter_mut().flat_map(|p| p.exercises.iter_mut().chain(p.finishers.iter_mut())) {
+        if db.id_of(slot) == old_id {
+            *slot = slot.substituted_by(replacement);
+            swapped = true;
+        }
+    }
+    if !swapped {
+        return None;
+    }
+    save_active_routine(&routine);
+    Some(routine)
+}
 
-    let total_volume: f64 = exercises
+fn session_volume(exercises: &[ExerciseRecord]) -> f64 {
+    exercises
         .iter()
         .flat_map(|e| &e.sets)
-        .map(|s| s.weight * s.reps as f64)
-        .sum();
+        .map(|s| s.volume())
+        .sum()
+}
+
+/// Save a finished session and return the personal records it set
+pub fn save_session(routine_name: String, exercises: Vec<ExerciseRecord>, duration_secs: i64) -> Vec<crate::stats::PersonalRecord> {
+    let mut db = load_data();
+
+    let total_volume = session_volume(&exercises);
+    let timestamp = chrono::Utc::now().timestamp();
+    let prs = crate::stats::detect_prs(&db, &exercises, timestamp, load_e1rm_formula());
 
     let session = Session {
         id: uuid_simple(),
         routine: routine_name,
-        timestamp: chrono::Utc::now().timestamp(),
+        timestamp,
         duration_secs,
         exercises,
         total_volume,
+        synced: false,
     };
 
     // Save last weights to cloud
     for ex in &session.exercises {
-        if let Some(last_set) = ex.sets.last() {
+        if let Some(last_set) = ex.last_working_set() {
             crate::supabase::save_weight_to_cloud(&ex.name, last_set.weight, last_set.reps);
         }
     }
@@ -353,9 +773,171 @@ pub fn save_session(routine_name: String, exercises: Vec<ExerciseRecord>, durati
     // Save session to cloud
     crate::supabase::save_session_to_cloud(&session);
 
+    update_prescriptions(&mut db, &session);
+    if let Some(routine) = clear_deloads(&db, &session) {
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = crate::supabase::save_routine(&routine).await;
+        });
+    }
+
     // Save locally (instant, works offline)
     db.add_session(session);
     let _ = save_data(&db);
+    prs
+}
+
+/// Run the progression engine for each exercise of a finished session.
+/// Rep targets come from the active routine's pass with the same name.
+fn update_prescriptions(db: &mut Database, session: &Session) {
+    let routine = load_active_routine().unwrap_or_else(create_default_routine);
+    let unit = load_weight_unit();
+    let pass = match routine.passes.iter().find(|p| p.name == session.routine) {
+        Some(p) => p,
+        None => return,
+    };
+
+    let mut updates: Vec<(String, Option<Prescription>)> = Vec::new();
+    for record in &session.exercises {
+        let ex = match pass.exercises.iter().chain(pass.finishers.iter()).find(|e| db.id_of(e) == record.exercise_id) {
+            Some(e) => e,
+            None => continue,
+        };
+        if ex.is_bodyweight || ex.is_cardio || ex.duration_secs.is_some() {
+            continue;
+        }
+
+        let mut earlier: Vec<(i64, &ExerciseRecord)> = db.sessions.iter()
+            .filter_map(|s| s.exercise(&record.exercise_id).map(|e| (s.timestamp, e)))
+            .collect();
+        earlier.sort_by_key(|(ts, _)| std::cmp::Reverse(*ts));
+        let history: Vec<&ExerciseRecord> = earlier.into_iter().map(|(_, e)| e).collect();
+
+        let prescription = crate::stats::prescribe_progression(record, &history, &ex.reps_target, ex.weight_step(unit), unit)
+            .map(|p| Prescription { weight: ex.clamp_weight(p.weight), ..p });
+        updates.push((record.exercise_id.clone(), prescription));
+    }
+
+    for (id, prescription) in updates {
+        match prescription {
+            Some(p) => { db.prescriptions.insert(id, p); }
+            None => { db.prescriptions.remove(&id); }
+        }
+    }
+}
+
+/// Replace a session edited in the history screen.
+/// Volume, last weights and prescriptions are recomputed; the cloud copy is upserted.
+pub fn update_session(mut session: Session) {
+    let mut db = load_data();
+    let pos = match db.sessions.iter().position(|s| s.id == session.id) {
+        Some(p) => p,
+        None => return,
+    };
+
+    session.exercises.retain(|e| !e.sets.is_empty());
+    session.total_volume = session_volume(&session.exercises);
+    session.synced = false;
+
+    let old = std::mem::replace(&mut db.sessions[pos], session.clone());
+    if !db.pending_session_edits.contains(&session.id) {
+        db.pending_session_edits.push(session.id.clone());
+    }
+    refresh_after_history_change(&mut db, &old, Some(&session));
+    let _ = save_data(&db);
+
+    crate::supabase::push_session_edit(&session);
+}
+
+/// Delete a session locally and in the cloud. Remembered until Supabase confirms,
+/// so a sync can't bring it back.
+pub fn delete_session(id: &str) {
+    let mut db = load_data();
+    let pos = match db.sessions.iter().position(|s| s.id == id) {
+        Some(p) => p,
+        None => return,
+    };
+
+    let old = db.sessions.remove(pos);
+    db.pending_session_edits.retain(|e| e != id);
+    if !db.pending_session_deletes.iter().any(|d| d == id) {
+        db.pending_session_deletes.push(id.to_string());
+    }
+    refresh_after_history_change(&mut db, &old, None);
+    let _ = save_data(&db);
+
+    crate::supabase::delete_session_from_cloud(id);
+}
+
+/// Cloud confirmed a session upsert
+pub fn mark_session_synced(id: &str) {
+    let mut db = load_data();
+    if let Some(s) = db.sessions.iter_mut().find(|s| s.id == id) {
+        s.synced = true;
+    }
+    db.pending_session_edits.retain(|e| e != id);
+    let _ = save_data(&db);
+}
+
+/// Cloud confirmed a session deletion
+pub fn mark_session_deleted(id: &str) {
+    let mut db = load_data();
+    db.pending_session_deletes.retain(|d| d != id);
+    let _ = save_data(&db);
+}
+
+/// Drop a last-weight delete from the retry queue once Supabase has it
+pub fn mark_weight_deleted(name: &str) {
+    let mut db = load_data();
+    db.pending_weight_deletes.retain(|n| n != name);
+    let _ = save_data(&db);
+}
+
+/// Recompute what was derived from a session that was edited (`new`) or deleted
+fn refresh_after_history_change(db: &mut Database, old: &Session, new: Option<&Session>) {
+    let mut names: Vec<String> = old.exercises.iter()
+        .chain(new.iter().flat_map(|s| s.exercises.iter()))
+        .map(|e| e.name.clone())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    for name in &names {
+        match db.latest_working_set(name) {
+            Some(set) => {
+                db.last_weights.insert(name.clone(), LastExerciseData { weight: set.weight, reps: set.reps });
+                db.pending_weight_deletes.retain(|n| n != name);
+                crate::supabase::save_weight_to_cloud(name, set.weight, set.reps);
+            }
+            None => {
+                db.last_weights.remove(name);
+                if !db.pending_weight_deletes.contains(name) {
+                    db.pending_weight_deletes.push(name.clone());
+                }
+                crate::supabase::delete_weight_from_cloud(name);
+            }
+        }
+
+        // A prescription may have been derived from the changed session
+        let id = db.exercise_id(name);
+        let later_session_exists = db.sessions.iter()
+            .any(|s| s.timestamp > old.timestamp && s.exercise(&id).is_some());
+        if !later_session_exists {
+            db.prescriptions.remove(&id);
+        }
+    }
+
+    // Re-run the engine on an edited session for exercises it's still the latest for
+    if let Some(session) = new {
+        let mut latest = session.clone();
+        latest.exercises.retain(|e| {
+            !db.sessions.iter().any(|s| s.timestamp > session.timestamp && s.exercise(&e.exercise_id).is_some())
+        });
+        if let Some(pos) = db.sessions.iter().position(|s| s.id == session.id) {
+            let current = db.sessions.remove(pos);
+            update_prescriptions(db, &latest);
+            db.sessions.insert(pos, current);
+        }
+    }
 }
 
 fn uuid_simple() -> String {
@@ -9,7 +9,8 @@ const AUTH_SESSION_KEY: &str = "oxidize_auth_session";
 const LAST_ACTIVITY_KEY: &str = "oxidize_last_activity";
 const INACTIVITY_TIMEOUT_SECS: i64 = 4 * 60 * 60; // 4 hours
 
-use crate::types::{Session, AuthSession, AuthUser, SavedRoutine, Pass};
+use crate::types::{Session, AuthSession, AuthUser, SavedRoutine, Pass, PlateSettings, WeightUnit, AthleteProfile, Sex};
+use crate::stats::E1rmFormula;
 
 // ============ AUTH ============
 
@@ -379,6 +380,7 @@ pub fn save_session_to_cloud(session: &Session) {
                 Ok(_) => {
                     web_sys::console::log_1(&format!("✓ Session {} saved to cloud", session.id).into());
                     clear_sync_failed_flag();
+                    crate::storage::mark_session_synced(&session.id);
                     return; // Success!
                 }
                 Err(e) => {
@@ -491,6 +493,85 @@ async fn upsert_session(session: &Session) -> Result<(), String> {
     Ok(())
 }
 
+/// Push a session edited in the history screen.
+/// On failure it stays pending and the next sync retries it.
+pub fn push_session_edit(session: &Session) {
+    if get_current_user_id().is_none() {
+        return;
+    }
+    let session = session.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        match upsert_session(&session).await {
+            Ok(_) => crate::storage::mark_session_synced(&session.id),
+            Err(e) => web_sys::console::log_1(&format!("Session edit push failed: {}", e).into()),
+        }
+    });
+}
+
+/// Delete a session from Supabase.
+/// On failure the tombstone stays and the next sync retries it.
+pub fn delete_session_from_cloud(session_id: &str) {
+    if get_current_user_id().is_none() {
+        return;
+    }
+    let session_id = session_id.to_string();
+    wasm_bindgen_futures::spawn_local(async move {
+        match delete_session(&session_id).await {
+            Ok(_) => crate::storage::mark_session_deleted(&session_id),
+            Err(e) => web_sys::console::log_1(&format!("Session delete failed: {}", e).into()),
+        }
+    });
+}
+
+async fn delete_session(session_id: &str) -> Result<(), String> {
+    let user_id = get_current_user_id().ok_or("Not logged in")?;
+    let headers = get_headers().map_err(|e| format!("{:?}", e))?;
+
+    let opts = create_request_init("DELETE", None, &headers);
+    let url = format!("{}/rest/v1/sessions?id=eq.{}&user_id=eq.{}", SUPABASE_URL, session_id, user_id);
+    let request = Request::new_with_str_and_init(&url, &opts).map_err(|e| format!("{:?}", e))?;
+
+    let resp = fetch_with_timeout(request, 5000).await?;
+    if !resp.ok() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    Ok(())
+}
+
+/// Remove an exercise's last weight from Supabase (no sessions left with it)
+pub fn delete_weight_from_cloud(exercise_name: &str) {
+    if get_current_user_id().is_none() {
+        return;
+    }
+    let exercise_name = exercise_name.to_string();
+    wasm_bindgen_futures::spawn_local(async move {
+        match delete_weight(&exercise_name).await {
+            Ok(_) => crate::storage::mark_weight_deleted(&exercise_name),
+            Err(e) => web_sys::console::log_1(&format!("Supabase weight delete failed: {}", e).into()),
+        }
+    });
+}
+
+async fn delete_weight(exercise_name: &str) -> Result<(), String> {
+    let user_id = get_current_user_id().ok_or("Not logged in")?;
+    let headers = get_headers().map_err(|e| format!("{:?}", e))?;
+
+    let opts = create_request_init("DELETE", None, &headers);
+    let url = format!(
+        "{}/rest/v1/last_weights?exercise_name=eq.{}&user_id=eq.{}",
+        SUPABASE_URL,
+        js_sys::encode_uri_component(exercise_name),
+        user_id
+    );
+    let request = Request::new_with_str_and_init(&url, &opts).map_err(|e| format!("{:?}", e))?;
+
+    let resp = fetch_with_timeout(request, 5000).await?;
+    if !resp.ok() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    Ok(())
+}
+
 /// Save last weight to Supabase
 pub fn save_weight_to_cloud(exercise_name: &str, weight: f64, reps: u8) {
     let exercise_name = exercise_name.to_string();
@@ -566,6 +647,7 @@ pub async fn fetch_sessions() -> Result<Vec<Session>, JsValue> {
             duration_secs: row.duration_secs,
             total_volume: row.total_volume,
             exercises,
+            synced: true,
         })
     }).collect();
     
@@ -631,19 +713,7 @@ async fn save_bodyweight_async(weight: f64) -> Result<(), JsValue> {
     let _ = JsFuture::from(window.fetch_with_request(&request)).await?;
 
     // 2. Save to settings table (user_settings) for CURRENT weight
-    // Create a row with ONLY user_id and bodyweight to avoid clobbering display_name
-    let settings_row = UserSettingsRow {
-        user_id: Some(user_id.clone()),
-        display_name: None, // Will be skipped during serialization
-        bodyweight: Some(weight),
-    };
-    let settings_body = serde_json::to_string(&settings_row).map_err(|e| e.to_string())?;
-    let settings_headers = get_headers()?;
-    settings_headers.set("Prefer", "resolution=merge-duplicates")?;
-    let settings_opts = create_request_init("POST", Some(&settings_body), &settings_headers);
-    let settings_url = format!("{}/rest/v1/user_settings", SUPABASE_URL);
-    let settings_request = Request::new_with_str_and_init(&settings_url, &settings_opts)?;
-    let _ = JsFuture::from(window.fetch_with_request(&settings_request)).await?;
+    upsert_user_settings(&UserSettingsRow { bodyweight: Some(weight), ..Default::default() }).await?;
     
     Ok(())
 }
@@ -655,17 +725,7 @@ pub async fn fetch_bodyweight() -> Result<(Option<f64>, Vec<crate::storage::Body
     let headers = get_headers()?;
 
     // 1. Fetch current weight from user_settings
-    let settings_opts = create_request_init("GET", None, &headers);
-    let settings_url = format!("{}/rest/v1/user_settings?user_id=eq.{}&select=user_id,bodyweight", SUPABASE_URL, user_id);
-    let settings_request = Request::new_with_str_and_init(&settings_url, &settings_opts)?;
-    let settings_resp: Response = JsFuture::from(window.fetch_with_request(&settings_request)).await?.dyn_into()?;
-    
-    let mut current_weight = None;
-    if settings_resp.ok() {
-        let json = JsFuture::from(settings_resp.json()?).await?;
-        let rows: Vec<UserSettingsRow> = serde_wasm_bindgen::from_value(json).unwrap_or_default();
-        current_weight = rows.first().and_then(|r| r.bodyweight);
-    }
+    let mut current_weight = fetch_user_settings("bodyweight").await?.and_then(|r| r.bodyweight);
 
     // 2. Fetch history from bodyweight table
     let history_opts = create_request_init("GET", None, &headers);
@@ -727,13 +787,47 @@ async fn do_sync() -> Result<(), JsValue> {
     // Check what's in local storage BEFORE sync
     let local_before = crate::storage::load_data();
     web_sys::console::log_1(&format!("LOCAL BEFORE: {} sessions", local_before.sessions.len()).into());
+
+    // PHASE 0: Retry history edits and deletions that never reached the cloud
+    let mut pending_deletes = local_before.pending_session_deletes.clone();
+    let mut pending_edits = local_before.pending_session_edits.clone();
+    for id in local_before.pending_session_deletes.iter() {
+        if delete_session(id).await.is_ok() {
+            pending_deletes.retain(|d| d != id);
+        }
+    }
+    let mut pending_weight_deletes = local_before.pending_weight_deletes.clone();
+    for name in local_before.pending_weight_deletes.iter() {
+        if delete_weight(name).await.is_ok() {
+            pending_weight_deletes.retain(|n| n != name);
+        }
+    }
+    for session in local_before.sessions.iter().filter(|s| local_before.pending_session_edits.contains(&s.id)) {
+        if upsert_session(session).await.is_ok() {
+            pending_edits.retain(|e| e != &session.id);
+        }
+    }
     
     // Fetch from cloud
     web_sys::console::log_1(&"Fetching from Supabase...".into());
     let cloud_sessions = fetch_sessions().await.unwrap_or_default();
-    let cloud_weights = fetch_last_weights().await.unwrap_or_default();
+    let mut cloud_weights = fetch_last_weights().await.unwrap_or_default();
+    cloud_weights.retain(|name, _| !pending_weight_deletes.contains(name));
     let (cloud_bodyweight, cloud_bw_history) = fetch_bodyweight().await.unwrap_or((None, vec![]));
+    let cloud_plates = fetch_plate_settings().await.unwrap_or(None);
     let cloud_display_name = fetch_display_name().await.unwrap_or(None);
+    if let Some(unit) = fetch_weight_unit().await.unwrap_or(None) {
+        crate::storage::save_weight_unit(unit);
+    }
+    if let Some(lifts) = fetch_big_lifts().await.unwrap_or(None) {
+        crate::storage::save_big_lifts(&lifts);
+    }
+    if let Some(formula) = fetch_e1rm_formula().await.unwrap_or(None) {
+        crate::storage::save_e1rm_formula(formula);
+    }
+    if let Some(profile) = fetch_profile().await.unwrap_or(None) {
+        crate::storage::save_profile(&profile);
+    }
     
     // Save display name to local storage if fetched from cloud
     if let Some(name) = &cloud_display_name {
@@ -754,11 +848,12 @@ async fn do_sync() -> Result<(), JsValue> {
     let mut pushed_count = 0;
     
     for local_session in &local_before.sessions {
-        if !cloud_ids.contains(&local_session.id) {
+        // A synced session missing from the cloud was deleted on another device
+        if !cloud_ids.contains(&local_session.id) && !local_session.synced {
             web_sys::console::log_1(&format!("📤 Pushing local session: {} ({})", local_session.routine, local_session.id).into());
             match upsert_session(local_session).await {
                 Ok(_) => {
-                    web_sys::console::log_1(&format!("  ✓ Pushed successfully").into());
+                    web_sys::console::log_1(&"  ✓ Pushed successfully".to_string().into());
                     pushed_count += 1;
                 }
                 Err(e) => {
@@ -783,14 +878,38 @@ async fn do_sync() -> Result<(), JsValue> {
     for s in &cloud_sessions {
         web_sys::console::log_1(&format!("  - {} ({})", s.routine, s.id).into());
     }
+
+    // Local history changes still waiting for the cloud win over the cloud copy
+    let cloud_sessions: Vec<Session> = cloud_sessions.into_iter()
+        .filter(|s| !pending_deletes.contains(&s.id))
+        .map(|s| {
+            local_before.sessions.iter()
+                .find(|l| l.id == s.id && pending_edits.contains(&l.id))
+                .cloned()
+                .unwrap_or(s)
+        })
+        .collect();
     
     // Create fresh database with cloud data
-    let mut db = crate::storage::Database::default();
-    db.sessions = cloud_sessions;
-    db.last_weights = cloud_weights;
-    db.bodyweight = cloud_bodyweight;
-    db.bodyweight_history = cloud_bw_history;
-    db.sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    let mut db = crate::storage::Database {
+        sessions: cloud_sessions,
+        last_weights: cloud_weights,
+        bodyweight: cloud_bodyweight,
+        bodyweight_history: cloud_bw_history,
+        // Keep local plate setup if it was never pushed to the cloud
+        plate_settings: cloud_plates.unwrap_or(local_before.plate_settings.clone()),
+        // Prescriptions are derived on this device when sessions are saved
+        prescriptions: local_before.prescriptions.clone(),
+        pending_session_edits: pending_edits,
+        pending_session_deletes: pending_deletes,
+        pending_weight_deletes,
+        // Exercise aliases live on the device; routines carry renames between devices
+        exercise_aliases: local_before.exercise_aliases.clone(),
+        dismissed_plateaus: local_before.dismissed_plateaus.clone(),
+        migration_version: local_before.migration_version,
+    };
+    db.sessions.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+    db.migrate_exercise_ids();
 
     // Save to localStorage
     web_sys::console::log_1(&"Saving to localStorage...".into());
@@ -861,12 +980,6 @@ pub async fn fetch_routines() -> Result<Vec<SavedRoutine>, JsValue> {
     Ok(routines)
 }
 
-/// Get the active routine for the current user
-pub async fn get_active_routine() -> Result<Option<SavedRoutine>, JsValue> {
-    let routines = fetch_routines().await?;
-    Ok(routines.into_iter().find(|r| r.is_active))
-}
-
 /// Save a routine to Supabase
 pub async fn save_routine(routine: &SavedRoutine) -> Result<(), JsValue> {
     let window = web_sys::window().ok_or("no window")?;
@@ -987,9 +1100,11 @@ pub async fn delete_routine(routine_id: &str) -> Result<(), JsValue> {
     Ok(())
 }
 
-// ============ USER SETTINGS (Display Name) ============
+// ============ USER SETTINGS ============
 
-#[derive(Serialize, Deserialize, Debug)]
+/// One row per user. Unset fields are skipped when saving, so each setting
+/// can be upserted on its own without clobbering the others.
+#[derive(Serialize, Deserialize, Debug, Default)]
 struct UserSettingsRow {
     #[serde(default)]
     user_id: Option<String>,
@@ -997,41 +1112,38 @@ struct UserSettingsRow {
     display_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     bodyweight: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bar_weight: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plate_inventory: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight_unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    big_lifts: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    e1rm_formula: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    birth_year: Option<i32>,
 }
 
-/// Save display name to Supabase (partial update)
-pub fn save_display_name_to_cloud(name: &str) {
-    let name = name.to_string();
-    update_last_activity();
-    wasm_bindgen_futures::spawn_local(async move {
-        if let Err(e) = save_display_name_async(&name).await {
-            web_sys::console::log_1(&format!("Supabase display_name save failed: {:?}", e).into());
-        }
-    });
-}
-
-async fn save_display_name_async(name: &str) -> Result<(), JsValue> {
+/// Upsert the fields set in `row` for the current user (partial update)
+async fn upsert_user_settings(row: &impl Serialize) -> Result<(), JsValue> {
     let window = web_sys::window().ok_or("no window")?;
     let user_id = get_current_user_id().ok_or("Not logged in")?;
-    
-    // Create a row with ONLY user_id and display_name to avoid clobbering bodyweight
-    let row = UserSettingsRow {
-        user_id: Some(user_id.clone()),
-        display_name: Some(if name.is_empty() { " ".to_string() } else { name.to_string() }),
-        bodyweight: None, // Will be skipped during serialization
-    };
-    
-    let body = serde_json::to_string(&row).map_err(|e| e.to_string())?;
-    let headers = get_headers().map_err(|_| "Failed to get headers")?;
-    headers.set("Prefer", "resolution=merge-duplicates").map_err(|_| "Failed to set Prefer header")?;
-    
+    let mut row = serde_json::to_value(row).map_err(|e| e.to_string())?;
+    row["user_id"] = serde_json::Value::String(user_id);
+
+    let body = row.to_string();
+    let headers = get_headers()?;
+    headers.set("Prefer", "resolution=merge-duplicates")?;
+
     let opts = create_request_init("POST", Some(&body), &headers);
     let url = format!("{}/rest/v1/user_settings", SUPABASE_URL);
     let request = Request::new_with_str_and_init(&url, &opts)?;
-    
-    let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
-    let resp: Response = resp_value.dyn_into()?;
-    
+
+    let resp: Response = JsFuture::from(window.fetch_with_request(&request)).await?.dyn_into()?;
     if resp.ok() {
         Ok(())
     } else {
@@ -1040,30 +1152,139 @@ async fn save_display_name_async(name: &str) -> Result<(), JsValue> {
     }
 }
 
-/// Fetch display name from Supabase
-pub async fn fetch_display_name() -> Result<Option<String>, JsValue> {
+/// Upsert in the background, logging failures under `what`
+fn save_user_settings_to_cloud(what: &'static str, row: impl Serialize + 'static) {
+    update_last_activity();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(e) = upsert_user_settings(&row).await {
+            web_sys::console::log_1(&format!("Supabase {} save failed: {:?}", what, e).into());
+        }
+    });
+}
+
+/// The current user's settings row with only the `select`ed columns.
+/// Ok(None) if there is no row or the request was refused.
+async fn fetch_user_settings(select: &str) -> Result<Option<UserSettingsRow>, JsValue> {
     let window = web_sys::window().ok_or("no window")?;
     let user_id = get_current_user_id().ok_or("Not logged in")?;
-    
+
     let headers = get_headers()?;
     let opts = create_request_init("GET", None, &headers);
-    
-    let url = format!("{}/rest/v1/user_settings?user_id=eq.{}&select=user_id,display_name", SUPABASE_URL, user_id);
+    let url = format!("{}/rest/v1/user_settings?user_id=eq.{}&select=user_id,{}", SUPABASE_URL, user_id, select);
     let request = Request::new_with_str_and_init(&url, &opts)?;
-    
-    let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
-    let resp: Response = resp_value.dyn_into()?;
-    
+
+    let resp: Response = JsFuture::from(window.fetch_with_request(&request)).await?.dyn_into()?;
     if !resp.ok() {
         return Ok(None);
     }
-    
+
     let json = JsFuture::from(resp.json()?).await?;
-    web_sys::console::log_1(&format!("DEBUG: fetch_display_name JSON: {:?}", json).into());
     let rows: Vec<UserSettingsRow> = serde_wasm_bindgen::from_value(json).unwrap_or_default();
-    web_sys::console::log_1(&format!("DEBUG: fetch_display_name rows: {:?}", rows).into());
-    
-    Ok(rows.first().and_then(|r| r.display_name.clone()))
+    Ok(rows.into_iter().next())
+}
+
+// ============ USER SETTINGS (Display Name) ============
+
+/// Save display name to Supabase (partial update)
+pub fn save_display_name_to_cloud(name: &str) {
+    let row = UserSettingsRow {
+        // An empty name is stored as a space so it still overwrites the old one
+        display_name: Some(if name.is_empty() { " ".to_string() } else { name.to_string() }),
+        ..Default::default()
+    };
+    save_user_settings_to_cloud("display_name", row);
+}
+
+/// Fetch display name from Supabase
+pub async fn fetch_display_name() -> Result<Option<String>, JsValue> {
+    Ok(fetch_user_settings("display_name").await?.and_then(|r| r.display_name))
+}
+
+// ============ USER SETTINGS (Plates) ============
+
+/// Save bar weight and plate inventory to Supabase (partial update)
+pub fn save_plate_settings_to_cloud(settings: &PlateSettings) {
+    let row = UserSettingsRow {
+        bar_weight: Some(settings.bar_weight),
+        plate_inventory: serde_json::to_value(&settings.plates).ok(),
+        ..Default::default()
+    };
+    save_user_settings_to_cloud("plate settings", row);
+}
+
+/// Fetch bar weight and plate inventory. Ok(None) if the user never saved any.
+pub async fn fetch_plate_settings() -> Result<Option<PlateSettings>, JsValue> {
+    let Some(row) = fetch_user_settings("bar_weight,plate_inventory").await? else { return Ok(None) };
+    let (Some(bar_weight), Some(inventory)) = (row.bar_weight, row.plate_inventory) else { return Ok(None) };
+    let plates = serde_json::from_value(inventory).unwrap_or_default();
+    Ok(Some(PlateSettings { bar_weight, plates }))
+}
+
+// ============ USER SETTINGS (Weight unit) ============
+
+/// Save kg/lb preference to Supabase (partial update)
+pub fn save_weight_unit_to_cloud(unit: WeightUnit) {
+    let row = UserSettingsRow { weight_unit: Some(unit.label().to_string()), ..Default::default() };
+    save_user_settings_to_cloud("weight_unit", row);
+}
+
+/// Fetch kg/lb preference. Ok(None) if the user never picked one.
+pub async fn fetch_weight_unit() -> Result<Option<WeightUnit>, JsValue> {
+    Ok(fetch_user_settings("weight_unit").await?.and_then(|r| r.weight_unit).map(|u| WeightUnit::parse(&u)))
+}
+
+// ============ USER SETTINGS (Big lifts) ============
+
+/// Save the exercise ids counted in the strength total to Supabase (partial update)
+pub fn save_big_lifts_to_cloud(lifts: &[String]) {
+    let row = UserSettingsRow { big_lifts: Some(lifts.to_vec()), ..Default::default() };
+    save_user_settings_to_cloud("big_lifts", row);
+}
+
+/// Fetch the strength total lifts. Ok(None) if the user never picked any.
+pub async fn fetch_big_lifts() -> Result<Option<Vec<String>>, JsValue> {
+    Ok(fetch_user_settings("big_lifts").await?.and_then(|r| r.big_lifts))
+}
+
+// ============ USER SETTINGS (E1RM formula) ============
+
+/// Save the 1RM estimation formula to Supabase (partial update)
+pub fn save_e1rm_formula_to_cloud(formula: E1rmFormula) {
+    let row = UserSettingsRow { e1rm_formula: Some(formula.label().to_string()), ..Default::default() };
+    save_user_settings_to_cloud("e1rm_formula", row);
+}
+
+/// Fetch the 1RM estimation formula. Ok(None) if the user never picked one.
+pub async fn fetch_e1rm_formula() -> Result<Option<E1rmFormula>, JsValue> {
+    Ok(fetch_user_settings("e1rm_formula").await?.and_then(|r| r.e1rm_formula).map(|f| E1rmFormula::parse(&f)))
+}
+
+// ============ USER SETTINGS (Profile) ============
+
+/// Profile columns without `skip_serializing_if`, so a cleared value is saved as null
+#[derive(Serialize)]
+struct ProfileSettingsRow {
+    sex: Option<String>,
+    birth_year: Option<i32>,
+}
+
+/// Save sex and birth year to Supabase (partial update)
+pub fn save_profile_to_cloud(profile: &AthleteProfile) {
+    let row = ProfileSettingsRow {
+        sex: profile.sex.map(|s| s.key().to_string()),
+        birth_year: profile.birth_year,
+    };
+    save_user_settings_to_cloud("profile", row);
+}
+
+/// Fetch sex and birth year. Ok(None) if neither was ever saved.
+pub async fn fetch_profile() -> Result<Option<AthleteProfile>, JsValue> {
+    let Some(row) = fetch_user_settings("sex,birth_year").await? else { return Ok(None) };
+    let profile = AthleteProfile {
+        sex: row.sex.as_deref().and_then(Sex::parse),
+        birth_year: row.birth_year,
+    };
+    Ok((profile != AthleteProfile::default()).then_some(profile))
 }
 
 // ============ AI AGENT ============
@@ -1137,7 +1358,7 @@ pub async fn call_gemini(api_key: &str, system_prompt: &str, user_prompt: &str)
         "https://generativelanguage.googleapis.com/v1beta/models?key={}",
         api_key
     );
-    web_sys::console::log_1(&format!("DEBUG: Listing available models...").into());
+    web_sys::console::log_1(&"DEBUG: Listing available models...".to_string().into());
     
     let list_headers = Headers::new()?;
     let list_opts = RequestInit::new();
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 pub struct Exercise {
     pub name: String,
     #[serde(default)]
+    pub id: Option<String>,  // Stable catalog id; None = derived from the name
+    #[serde(default)]
     pub sets: u8,
     #[serde(default)]
     pub reps_target: String,
@@ -14,6 +16,8 @@ pub struct Exercise {
     #[serde(default)]
     pub superset_name: Option<String>,
     #[serde(default)]
+    pub group: Option<String>,  // Shared by every member of a superset, tri-set or circuit
+    #[serde(default)]
     pub is_bodyweight: bool,
     #[serde(default)]
     pub duration_secs: Option<u32>,  // Some(30) = timed exercise, None = reps-based
@@ -28,17 +32,259 @@ pub struct Exercise {
     pub equipment: Option<String>,
     #[serde(default)]
     pub wger_id: Option<u32>,
+    #[serde(default)]
+    pub warmup: WarmupScheme,
+    #[serde(default)]
+    pub weight_step: Option<f64>,  // None = default for the equipment
+    #[serde(default)]
+    pub min_weight: Option<f64>,
+    #[serde(default)]
+    pub max_weight: Option<f64>,
+    #[serde(default)]
+    pub rest_secs: Option<u32>,  // None = default by rep range
+    #[serde(default)]
+    pub is_cardio: bool,  // Logged as distance/time intervals instead of weight × reps
+    #[serde(default)]
+    pub block: Option<BlockFormat>,  // Run on the clock; covers the exercise's whole group
+    #[serde(default)]
+    pub deload_weight: Option<f64>,  // Planned from a plateau warning; cleared once trained
+}
+
+/// Warm-up ramp proposed before the first working set
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmupScheme {
+    #[default]
+    Auto,  // Standard ramp for barbell lifts once the working weight is heavy enough
+    Off,
+    Short,
+    Standard,
+    Long,
+}
+
+impl WarmupScheme {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WarmupScheme::Auto => "Auto",
+            WarmupScheme::Off => "Av",
+            WarmupScheme::Short => "Kort",
+            WarmupScheme::Standard => "Standard",
+            WarmupScheme::Long => "Lång",
+        }
+    }
+
+    /// Next scheme when cycling through them in the routine builder
+    pub fn next(&self) -> Self {
+        match self {
+            WarmupScheme::Auto => WarmupScheme::Off,
+            WarmupScheme::Off => WarmupScheme::Short,
+            WarmupScheme::Short => WarmupScheme::Standard,
+            WarmupScheme::Standard => WarmupScheme::Long,
+            WarmupScheme::Long => WarmupScheme::Auto,
+        }
+    }
+
+    /// (fraction of working weight, reps) - 0.0 means the empty bar
+    pub fn steps(&self) -> &'static [(f64, u8)] {
+        match self {
+            WarmupScheme::Off => &[],
+            WarmupScheme::Short => &[(0.0, 10), (0.6, 3)],
+            WarmupScheme::Auto | WarmupScheme::Standard => &[(0.0, 10), (0.4, 5), (0.6, 3), (0.8, 1)],
+            WarmupScheme::Long => &[(0.0, 10), (0.3, 8), (0.5, 5), (0.7, 3), (0.85, 1), (0.92, 1)],
+        }
+    }
+}
+
+/// Clock-driven block format, run on its own screen instead of set-by-set
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BlockFormat {
+    Emom { minutes: u32 },  // One set at the top of every minute
+    Amrap { minutes: u32 },  // As many rounds as possible before time runs out
+    Interval { work_secs: u32, rest_secs: u32, rounds: u32 },  // Tabata is 20/10 × 8
+}
+
+/// Where the clock is inside a block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockPhase {
+    pub interval: u32,  // 0-based minute (EMOM) or round (intervals); always 0 for AMRAP
+    pub working: bool,
+    pub remaining_secs: u32,  // Left of the current phase
+}
+
+impl BlockFormat {
+    pub fn tabata() -> Self {
+        BlockFormat::Interval { work_secs: 20, rest_secs: 10, rounds: 8 }
+    }
+
+    pub fn label(&self) -> String {
+        match *self {
+            BlockFormat::Emom { minutes } => format!("EMOM {} min", minutes),
+            BlockFormat::Amrap { minutes } => format!("AMRAP {} min", minutes),
+            BlockFormat::Interval { work_secs: 20, rest_secs: 10, rounds } => format!("Tabata × {}", rounds),
+            BlockFormat::Interval { work_secs, rest_secs, rounds } => format!("Intervall {}/{} × {}", work_secs, rest_secs, rounds),
+        }
+    }
+
+    /// Next format when cycling through them in the routine builder
+    pub fn next(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(BlockFormat::Emom { minutes: 10 }),
+            Some(BlockFormat::Emom { .. }) => Some(BlockFormat::Amrap { minutes: 10 }),
+            Some(BlockFormat::Amrap { .. }) => Some(BlockFormat::tabata()),
+            Some(BlockFormat::Interval { .. }) => None,
+        }
+    }
+
+    /// Editable numbers in the routine builder: (label, value)
+    pub fn fields(&self) -> Vec<(&'static str, u32)> {
+        match *self {
+            BlockFormat::Emom { minutes } | BlockFormat::Amrap { minutes } => vec![("Min", minutes)],
+            BlockFormat::Interval { work_secs, rest_secs, rounds } => {
+                vec![("Arbete s", work_secs), ("Vila s", rest_secs), ("Varv", rounds)]
+            }
+        }
+    }
+
+    /// Copy with field `i` (as listed by `fields`) set to `value`
+    pub fn with_field(self, i: usize, value: u32) -> Self {
+        let value = value.max(1);
+        match (self, i) {
+            (BlockFormat::Emom { .. }, _) => BlockFormat::Emom { minutes: value },
+            (BlockFormat::Amrap { .. }, _) => BlockFormat::Amrap { minutes: value },
+            (BlockFormat::Interval { rest_secs, rounds, .. }, 0) => BlockFormat::Interval { work_secs: value, rest_secs, rounds },
+            (BlockFormat::Interval { work_secs, rounds, .. }, 1) => BlockFormat::Interval { work_secs, rest_secs: value, rounds },
+            (BlockFormat::Interval { work_secs, rest_secs, .. }, _) => BlockFormat::Interval { work_secs, rest_secs, rounds: value },
+        }
+    }
+
+    pub fn total_secs(&self) -> u32 {
+        match *self {
+            BlockFormat::Emom { minutes } | BlockFormat::Amrap { minutes } => minutes * 60,
+            BlockFormat::Interval { work_secs, rest_secs, rounds } => (work_secs + rest_secs) * rounds,
+        }
+    }
+
+    /// Phase `elapsed` seconds into the block, None once it's over
+    pub fn phase_at(&self, elapsed: u32) -> Option<BlockPhase> {
+        if elapsed >= self.total_secs() {
+            return None;
+        }
+        Some(match *self {
+            BlockFormat::Emom { .. } => BlockPhase { interval: elapsed / 60, working: true, remaining_secs: 60 - elapsed % 60 },
+            BlockFormat::Amrap { .. } => BlockPhase { interval: 0, working: true, remaining_secs: self.total_secs() - elapsed },
+            BlockFormat::Interval { work_secs, rest_secs, .. } => {
+                let within = elapsed % (work_secs + rest_secs);
+                BlockPhase {
+                    interval: elapsed / (work_secs + rest_secs),
+                    working: within < work_secs,
+                    remaining_secs: if within < work_secs { work_secs - within } else { work_secs + rest_secs - within },
+                }
+            }
+        })
+    }
+}
+
+/// Sensible weight step (in kg) for a Wger equipment name and the gym's unit.
+/// Wger has no machine or cable equipment, so those get the barbell step;
+/// set `Exercise::weight_step` to match a machine's stack.
+pub fn default_weight_step(equipment: Option<&str>, unit: WeightUnit) -> f64 {
+    let equipment = equipment.unwrap_or_default().to_lowercase();
+    let (kettlebell, dumbbell, barbell) = match unit {
+        WeightUnit::Kg => (4.0, 2.0, 2.5),
+        WeightUnit::Lb => (10.0, 5.0, 5.0),
+    };
+    let step = if equipment.contains("kettlebell") {
+        kettlebell
+    } else if equipment.contains("dumbbell") {
+        dumbbell
+    } else {
+        barbell
+    };
+    unit.to_kg(step)
 }
 
 impl Exercise {
+    /// Weight change (kg) for the +/− buttons and automatic progression
+    pub fn weight_step(&self, unit: WeightUnit) -> f64 {
+        self.weight_step
+            .filter(|s| *s > 0.0)
+            .unwrap_or_else(|| default_weight_step(self.equipment.as_deref(), unit))
+    }
+
+    /// Loaded on a barbell: by Wger equipment, else by the catalog's barbell lifts
+    pub fn is_barbell(&self) -> bool {
+        match &self.equipment {
+            Some(equipment) => {
+                let equipment = equipment.to_lowercase();
+                equipment.contains("barbell") || equipment.contains("sz-bar")
+            }
+            None => self.id.as_deref()
+                .or_else(|| crate::catalog::lookup(&self.name).map(|e| e.id))
+                .is_some_and(|id| crate::catalog::BARBELL_LIFTS.contains(&id)),
+        }
+    }
+
+    /// Target rest before each set. Heavy low-rep work gets the longest rest.
+    pub fn rest_target_secs(&self) -> u32 {
+        if let Some(secs) = self.rest_secs {
+            return secs;
+        }
+        if self.duration_secs.is_some() || self.is_cardio {
+            return 60;
+        }
+        match crate::app::parse_target_range(&self.reps_target).1 {
+            0..=6 => 180,
+            7..=12 => 120,
+            _ => 60,
+        }
+    }
+
+    /// Keep a weight inside the exercise's optional min/max
+    pub fn clamp_weight(&self, weight: f64) -> f64 {
+        let weight = weight.max(self.min_weight.unwrap_or(0.0));
+        match self.max_weight {
+            Some(max) if max > 0.0 => weight.min(max),
+            _ => weight,
+        }
+    }
+
+    /// Group this exercise rotates with. Older routines only link supersets as
+    /// pairs through `superset_with`, which maps to a key shared by both partners.
+    pub fn group_key(&self) -> Option<String> {
+        if self.group.is_some() {
+            return self.group.clone();
+        }
+        let partner = self.superset_with.as_ref().filter(|_| self.is_superset)?;
+        let (a, b) = if self.name <= *partner { (&self.name, partner) } else { (partner, &self.name) };
+        Some(format!("{}+{}", a, b))
+    }
+
+    /// `other` done in this exercise's slot: keeps sets, reps, rest, group and block
+    pub fn substituted_by(&self, other: &Exercise) -> Exercise {
+        Exercise {
+            sets: self.sets,
+            reps_target: self.reps_target.clone(),
+            is_superset: self.is_superset,
+            superset_with: self.superset_with.clone(),
+            superset_name: self.superset_name.clone(),
+            group: self.group.clone(),
+            block: self.block,
+            rest_secs: self.rest_secs,
+            ..other.clone()
+        }
+    }
+
     pub fn standard(name: &str, sets: u8, reps: &str) -> Self {
         Self {
             name: name.to_string(),
+            id: None,
             sets,
             reps_target: reps.to_string(),
             is_superset: false,
             superset_with: None,
             superset_name: None,
+            group: None,
             is_bodyweight: false,
             duration_secs: None,
             primary_muscles: vec![],
@@ -46,17 +292,27 @@ impl Exercise {
             image_url: None,
             equipment: None,
             wger_id: None,
+            warmup: WarmupScheme::Auto,
+            weight_step: None,
+            min_weight: None,
+            max_weight: None,
+            rest_secs: None,
+            is_cardio: false,
+            block: None,
+            deload_weight: None,
         }
     }
 
     pub fn superset(name: &str, sets: u8, reps: &str, partner: &str, ss_name: Option<&str>) -> Self {
         Self {
             name: name.to_string(),
+            id: None,
             sets,
             reps_target: reps.to_string(),
             is_superset: true,
             superset_with: Some(partner.to_string()),
             superset_name: ss_name.map(|s| s.to_string()),
+            group: None,
             is_bodyweight: false,
             duration_secs: None,
             primary_muscles: vec![],
@@ -64,17 +320,27 @@ impl Exercise {
             image_url: None,
             equipment: None,
             wger_id: None,
+            warmup: WarmupScheme::Auto,
+            weight_step: None,
+            min_weight: None,
+            max_weight: None,
+            rest_secs: None,
+            is_cardio: false,
+            block: None,
+            deload_weight: None,
         }
     }
     
     pub fn finisher(name: &str, sets: u8, reps: &str) -> Self {
         Self {
             name: name.to_string(),
+            id: None,
             sets,
             reps_target: reps.to_string(),
             is_superset: false,
             superset_with: None,
             superset_name: None,
+            group: None,
             is_bodyweight: true,
             duration_secs: None,
             primary_muscles: vec![],
@@ -82,17 +348,27 @@ impl Exercise {
             image_url: None,
             equipment: None,
             wger_id: None,
+            warmup: WarmupScheme::Auto,
+            weight_step: None,
+            min_weight: None,
+            max_weight: None,
+            rest_secs: None,
+            is_cardio: false,
+            block: None,
+            deload_weight: None,
         }
     }
     
     pub fn timed_finisher(name: &str, sets: u8, duration: u32) -> Self {
         Self {
             name: name.to_string(),
+            id: None,
             sets,
             reps_target: format!("{} sek", duration),
             is_superset: false,
             superset_with: None,
             superset_name: None,
+            group: None,
             is_bodyweight: true,
             duration_secs: Some(duration),
             primary_muscles: vec![],
@@ -100,36 +376,94 @@ impl Exercise {
             image_url: None,
             equipment: None,
             wger_id: None,
+            warmup: WarmupScheme::Auto,
+            weight_step: None,
+            min_weight: None,
+            max_weight: None,
+            rest_secs: None,
+            is_cardio: false,
+            block: None,
+            deload_weight: None,
         }
     }
     
-    /// Create exercise from Wger API data
-    pub fn from_wger(
-        name: &str,
-        sets: u8,
-        reps: &str,
-        primary_muscles: Vec<String>,
-        secondary_muscles: Vec<String>,
-        image_url: Option<String>,
-        equipment: Option<String>,
-        wger_id: u32,
-    ) -> Self {
+    /// Create exercise from a Wger search result
+    pub fn from_wger(wger: &crate::wger::WgerExercise, sets: u8, reps: &str) -> Self {
         Self {
-            name: name.to_string(),
+            name: wger.name.clone(),
+            id: None,
             sets,
             reps_target: reps.to_string(),
             is_superset: false,
             superset_with: None,
             superset_name: None,
+            group: None,
             is_bodyweight: false,
             duration_secs: None,
-            primary_muscles,
-            secondary_muscles,
-            image_url,
-            equipment,
-            wger_id: Some(wger_id),
+            primary_muscles: wger.primary_muscles.clone(),
+            secondary_muscles: wger.secondary_muscles.clone(),
+            image_url: wger.image_url.clone(),
+            equipment: wger.equipment.clone(),
+            wger_id: Some(wger.base_id),
+            warmup: WarmupScheme::Auto,
+            weight_step: None,
+            min_weight: None,
+            max_weight: None,
+            rest_secs: None,
+            is_cardio: false,
+            block: None,
+            deload_weight: None,
+        }
+    }
+}
+
+/// What role a logged set played. Old data without a kind is treated as working sets.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SetKind {
+    Warmup,
+    #[default]
+    Working,
+    Drop,
+    Failure,
+    Backoff,
+}
+
+impl SetKind {
+    pub fn all() -> [SetKind; 5] {
+        [SetKind::Warmup, SetKind::Working, SetKind::Drop, SetKind::Failure, SetKind::Backoff]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SetKind::Warmup => "Uppvärmning",
+            SetKind::Working => "Arbetsset",
+            SetKind::Drop => "Dropset",
+            SetKind::Failure => "Till failure",
+            SetKind::Backoff => "Back-off",
         }
     }
+
+    /// Counts toward volume, weekly sets and the routine's set target (everything but warm-ups)
+    pub fn counts_as_work(&self) -> bool {
+        !matches!(self, SetKind::Warmup)
+    }
+
+    /// Top-effort sets used for last weights and load suggestions
+    pub fn is_working(&self) -> bool {
+        matches!(self, SetKind::Working | SetKind::Failure)
+    }
+}
+
+/// Distance, energy and heart rate of a cardio interval (time is `SetRecord::duration_secs`)
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CardioStats {
+    #[serde(default)]
+    pub distance_m: Option<f64>,
+    #[serde(default)]
+    pub calories: Option<u32>,
+    #[serde(default)]
+    pub avg_hr: Option<u16>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -138,6 +472,170 @@ pub struct SetRecord {
     pub reps: u8,
     pub timestamp: i64,
     pub rest_before_secs: Option<i64>,
+    #[serde(default)]
+    pub rpe: Option<f64>,  // Rate of perceived exertion 6-10 (10 = 0 reps in reserve)
+    #[serde(default)]
+    pub kind: SetKind,
+    #[serde(default)]
+    pub rest_target_secs: Option<u32>,  // Countdown shown before this set
+    #[serde(default)]
+    pub duration_secs: Option<u32>,  // Timed work (holds, intervals) - reps is 0
+    #[serde(default)]
+    pub cardio: Option<CardioStats>,
+}
+
+impl SetRecord {
+    /// Weight × reps, zero for warm-ups and timed sets
+    pub fn volume(&self) -> f64 {
+        if self.kind.counts_as_work() && !self.is_timed() { self.weight * self.reps as f64 } else { 0.0 }
+    }
+
+    pub fn is_timed(&self) -> bool {
+        self.duration_secs.is_some()
+    }
+
+    pub fn distance_m(&self) -> Option<f64> {
+        self.cardio.as_ref().and_then(|c| c.distance_m)
+    }
+
+    /// Seconds rested beyond the target (negative = cut the rest short)
+    pub fn rest_vs_target(&self) -> Option<i64> {
+        Some(self.rest_before_secs? - self.rest_target_secs? as i64)
+    }
+}
+
+/// Pairs of one plate size available in the gym
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PlateStock {
+    pub weight: f64,
+    pub pairs: u8,
+}
+
+/// Barbell and plate inventory used by the plate calculator (synced via user_settings)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PlateSettings {
+    pub bar_weight: f64,
+    pub plates: Vec<PlateStock>,
+}
+
+impl PlateSettings {
+    /// Typical gym setup for a unit: 20 kg bar with metric plates, or 45 lb bar with lb plates
+    pub fn standard(unit: WeightUnit) -> Self {
+        let (bar, plates): (f64, &[(f64, u8)]) = match unit {
+            WeightUnit::Kg => (20.0, &[(25.0, 4), (20.0, 2), (15.0, 2), (10.0, 2), (5.0, 2), (2.5, 2), (1.25, 2)]),
+            WeightUnit::Lb => (45.0, &[(45.0, 4), (35.0, 2), (25.0, 2), (10.0, 2), (5.0, 2), (2.5, 2)]),
+        };
+        Self {
+            bar_weight: unit.to_kg(bar),
+            plates: plates.iter()
+                .map(|&(weight, pairs)| PlateStock { weight: unit.to_kg(weight), pairs })
+                .collect(),
+        }
+    }
+}
+
+impl Default for PlateSettings {
+    fn default() -> Self {
+        Self::standard(WeightUnit::Kg)
+    }
+}
+
+pub const KG_PER_LB: f64 = 0.45359237;
+
+/// Unit weights are shown and entered in. Everything is stored in kg.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightUnit {
+    #[default]
+    Kg,
+    Lb,
+}
+
+impl WeightUnit {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lb => "lb",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        if s == "lb" { WeightUnit::Lb } else { WeightUnit::Kg }
+    }
+
+    /// Stored kg value in this unit
+    pub fn in_unit(self, kg: f64) -> f64 {
+        match self {
+            WeightUnit::Kg => kg,
+            WeightUnit::Lb => kg / KG_PER_LB,
+        }
+    }
+
+    /// Value entered in this unit, in kg
+    pub fn to_kg(self, value: f64) -> f64 {
+        match self {
+            WeightUnit::Kg => value,
+            WeightUnit::Lb => value * KG_PER_LB,
+        }
+    }
+}
+
+/// Sex used by the relative strength formulas
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+impl Sex {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Sex::Male => "male",
+            Sex::Female => "female",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Sex::Male => "Man",
+            Sex::Female => "Kvinna",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "male" => Some(Sex::Male),
+            "female" => Some(Sex::Female),
+            _ => None,
+        }
+    }
+}
+
+/// Who is lifting, for scores that compare across bodyweight, sex and age
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct AthleteProfile {
+    #[serde(default)]
+    pub sex: Option<Sex>,
+    #[serde(default)]
+    pub birth_year: Option<i32>,
+}
+
+/// Double-progression outcome for an exercise's next session
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressionDecision {
+    Increase,
+    Hold,
+    Deload,
+}
+
+/// Weight prescribed for the next session, with the reason shown in the workout
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Prescription {
+    pub weight: f64,
+    pub decision: ProgressionDecision,
+    pub reason: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -152,6 +650,19 @@ pub struct ExerciseWorkoutState {
     pub last_data: Option<LastExerciseData>,
     pub current_weight: f64,
     pub sets_completed: Vec<SetRecord>,
+    #[serde(default)]
+    pub weight_note: Option<String>,  // Why current_weight differs from last time (shown in workout)
+}
+
+impl ExerciseWorkoutState {
+    /// Completed sets that count toward `Exercise::sets` (warm-ups excluded)
+    pub fn working_sets_done(&self) -> usize {
+        self.sets_completed.iter().filter(|s| s.kind.counts_as_work()).count()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.working_sets_done() >= self.exercise.sets as usize
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -171,11 +682,46 @@ pub struct WorkoutData {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ExerciseRecord {
     pub name: String,
+    #[serde(default)]
+    pub exercise_id: String,  // Empty until migrated, see `Database::migrate_exercise_ids`
     pub sets: Vec<SetRecord>,
     #[serde(default)]
     pub primary_muscles: Vec<String>,
     #[serde(default)]
     pub secondary_muscles: Vec<String>,
+    #[serde(default)]
+    pub is_cardio: bool,
+}
+
+impl ExerciseRecord {
+    /// Sets that count as work (warm-ups excluded)
+    pub fn work_sets(&self) -> impl Iterator<Item = &SetRecord> {
+        self.sets.iter().filter(|s| s.kind.counts_as_work())
+    }
+
+    /// Total cardio distance and time over intervals that logged both
+    pub fn distance_and_time(&self) -> (f64, u32) {
+        self.work_sets()
+            .filter_map(|s| Some((s.distance_m()?, s.duration_secs?)))
+            .fold((0.0, 0), |(d, t), (sd, st)| (d + sd, t + st))
+    }
+
+    /// Average pace in seconds per km
+    pub fn pace_secs_per_km(&self) -> Option<f64> {
+        let (distance, secs) = self.distance_and_time();
+        (distance > 0.0 && secs > 0).then(|| secs as f64 / (distance / 1000.0))
+    }
+
+    /// Work sets measured in weight × reps (timed sets excluded), for E1RM
+    pub fn rep_sets(&self) -> impl Iterator<Item = &SetRecord> {
+        self.work_sets().filter(|s| !s.is_timed())
+    }
+
+    /// Last top-effort set, falling back to the last work set (e.g. only drop sets logged)
+    pub fn last_working_set(&self) -> Option<&SetRecord> {
+        self.sets.iter().rev().find(|s| s.kind.is_working())
+            .or_else(|| self.work_sets().last())
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -186,6 +732,15 @@ pub struct Session {
     pub duration_secs: i64,
     pub exercises: Vec<ExerciseRecord>,
     pub total_volume: f64,
+    #[serde(default)]
+    pub synced: bool,  // Local only: confirmed stored in Supabase
+}
+
+impl Session {
+    /// This session's record of the exercise with `exercise_id`, whatever it was called then
+    pub fn exercise(&self, exercise_id: &str) -> Option<&ExerciseRecord> {
+        self.exercises.iter().find(|e| e.exercise_id == exercise_id)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
@@ -203,8 +758,10 @@ pub enum AppView {
     Dashboard,
     Workout(String),
     Stats,
+    History,
     Settings,
     RoutineBuilder(Option<String>), // Some(id) = editing, None = new
+    ExerciseDetail(String, Option<String>), // Exercise id, pass to resume on back (None = stats)
 }
 
 /// Stored routine in Supabase
@@ -230,6 +787,84 @@ pub struct Pass {
     pub finishers: Vec<Exercise>,
 }
 
+/// Name of a group by its size
+pub fn group_label(members: usize) -> &'static str {
+    match members {
+        0..=2 => "Superset",
+        3 => "Tri-set",
+        _ => "Circuit",
+    }
+}
+
+impl Pass {
+    /// Indices of the exercises sharing `key`, in the order they're done
+    pub fn group_members(&self, key: &str) -> Vec<usize> {
+        self.exercises.iter().enumerate()
+            .filter(|(_, e)| e.group_key().as_deref() == Some(key))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Rounds in a group: the most sets any member has
+    pub fn group_rounds(&self, key: &str) -> u8 {
+        self.group_members(key).iter().map(|&i| self.exercises[i].sets).max().unwrap_or(0)
+    }
+
+    /// Bring groups into one shape: legacy pairs become groups, a group needs
+    /// two or more members, members sit next to each other and groups are
+    /// lettered A, B, C... (then AA, AB...) in the order they appear
+    pub fn normalize_groups(&mut self) {
+        let keys: Vec<Option<String>> = self.exercises.iter().map(|e| {
+            // A legacy link only counts if the partner links back
+            let linked = e.group.is_some() || e.superset_with.as_ref().is_some_and(|p| {
+                self.exercises.iter().any(|o| &o.name == p && o.superset_with.as_ref() == Some(&e.name))
+            });
+            e.group_key().filter(|_| linked)
+        }).collect();
+
+        let mut order: Vec<usize> = Vec::with_capacity(keys.len());
+        let mut letters: Vec<(String, String)> = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            if order.contains(&i) {
+                continue;
+            }
+            let members: Vec<usize> = match key {
+                Some(k) => keys.iter().enumerate().filter(|(_, o)| o.as_ref() == Some(k)).map(|(j, _)| j).collect(),
+                None => vec![i],
+            };
+            if let (Some(k), true) = (key, members.len() >= 2) {
+                letters.push((k.clone(), group_letter(letters.len())));
+                order.extend(members);
+            } else {
+                order.push(i);
+            }
+        }
+
+        let mut exercises: Vec<Option<Exercise>> = std::mem::take(&mut self.exercises).into_iter().map(Some).collect();
+        self.exercises = order.iter().filter_map(|&i| {
+            let mut ex = exercises[i].take()?;
+            ex.group = keys[i].as_ref()
+                .and_then(|k| letters.iter().find(|(key, _)| key == k))
+                .map(|(_, letter)| letter.clone());
+            ex.is_superset = ex.group.is_some();
+            ex.superset_with = None;
+            Some(ex)
+        }).collect();
+    }
+}
+
+/// Letters for the `n`th group (from 0): A-Z, then AA, AB... like spreadsheet columns
+fn group_letter(n: usize) -> String {
+    let mut label = String::new();
+    let mut n = n + 1;
+    while n > 0 {
+        n -= 1;
+        label.insert(0, (b'A' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    label
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AuthUser {
     pub id: String,
@@ -252,4 +887,10 @@ pub struct PausedWorkout {
     pub current_exercise_idx: usize,
     pub start_timestamp: i64,
     pub elapsed_secs: i64,
+    #[serde(default)]
+    pub last_set_time: i64,  // When the latest set was logged, for the next set's rest
+    #[serde(default)]
+    pub left_at: Option<i64>,  // Set when leaving for the exercise history mid-workout
+    #[serde(default)]
+    pub clock_running: bool,  // Came straight back from the history, so the time away counts
 }
@@ -0,0 +1,148 @@
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response;
+
+// Wger API types
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WgerExercise {
+    pub id: u32,
+    pub base_id: u32,
+    pub name: String,
+    pub primary_muscles: Vec<String>,
+    pub secondary_muscles: Vec<String>,
+    pub image_url: Option<String>,
+    pub equipment: Option<String>,
+}
+
+// Wger muscle ID → English name mapping
+// Source: https://wger.de/api/v2/muscle/?format=json
+fn wger_muscle_name(id: u32) -> &'static str {
+    match id {
+        1 => "Biceps brachii",
+        2 => "Anterior deltoid",
+        3 => "Serratus anterior",
+        4 => "Pectoralis major",
+        5 => "Obliquus externus abdominis",
+        6 => "Rectus abdominis",
+        7 => "Gastrocnemius",
+        8 => "Gluteus maximus",
+        9 => "Trapezius",
+        10 => "Quadriceps femoris",
+        11 => "Biceps femoris",
+        12 => "Latissimus dorsi",
+        13 => "Brachialis",
+        14 => "Obliquus externus abdominis",
+        15 => "Soleus",
+        _ => "Unknown",
+    }
+}
+
+/// Muscles and first listed equipment for a Wger exercise
+async fn fetch_wger_details(base_id: u32) -> (Vec<String>, Vec<String>, Option<String>) {
+    let window = match web_sys::window() {
+        Some(w) => w,
+        None => return (vec![], vec![], None),
+    };
+
+    let url = format!("https://wger.de/api/v2/exerciseinfo/{}/?format=json", base_id);
+    let resp_value = match JsFuture::from(window.fetch_with_str(&url)).await {
+        Ok(v) => v,
+        Err(_) => return (vec![], vec![], None),
+    };
+    let resp: Response = match resp_value.dyn_into() {
+        Ok(r) => r,
+        Err(_) => return (vec![], vec![], None),
+    };
+    if !resp.ok() { return (vec![], vec![], None); }
+
+    let json = match JsFuture::from(match resp.json() { Ok(j) => j, Err(_) => return (vec![], vec![], None) }).await {
+        Ok(j) => j,
+        Err(_) => return (vec![], vec![], None),
+    };
+
+    #[derive(Deserialize)]
+    struct WgerMuscle { id: u32 }
+    #[derive(Deserialize)]
+    struct WgerEquipment { name: String }
+    #[derive(Deserialize)]
+    struct WgerExerciseInfo {
+        muscles: Vec<WgerMuscle>,
+        #[serde(default)]
+        muscles_secondary: Vec<WgerMuscle>,
+        #[serde(default)]
+        equipment: Vec<WgerEquipment>,
+    }
+
+    let info: WgerExerciseInfo = match serde_wasm_bindgen::from_value(json) {
+        Ok(i) => i,
+        Err(_) => return (vec![], vec![], None),
+    };
+
+    let equipment = info.equipment.first().map(|e| e.name.clone());
+    let primary: Vec<String> = info.muscles.iter().map(|m| wger_muscle_name(m.id).to_string()).collect();
+    let secondary: Vec<String> = info.muscles_secondary.iter().map(|m| wger_muscle_name(m.id).to_string()).collect();
+    (primary, secondary, equipment)
+}
+
+pub async fn search_wger_exercises(query: &str) -> Result<Vec<WgerExercise>, JsValue> {
+    let window = web_sys::window().ok_or("no window")?;
+
+    let encoded_query = js_sys::encode_uri_component(query);
+    let url = format!("https://wger.de/api/v2/exercise/search/?language=2&term={}", encoded_query);
+    let resp_value = JsFuture::from(window.fetch_with_str(&url)).await?;
+    let resp: Response = resp_value.dyn_into()?;
+
+    if !resp.ok() {
+        return Ok(vec![]);
+    }
+
+    let json = JsFuture::from(resp.json()?).await?;
+
+    #[derive(Deserialize)]
+    struct WgerSearchResponse {
+        suggestions: Vec<WgerSuggestion>,
+    }
+
+    #[derive(Deserialize)]
+    struct WgerSuggestion {
+        data: WgerSuggestionData,
+    }
+
+    #[derive(Deserialize)]
+    struct WgerSuggestionData {
+        id: u32,
+        base_id: u32,
+        name: String,
+        image: Option<String>,
+    }
+
+    let search_resp: WgerSearchResponse = serde_wasm_bindgen::from_value(json).unwrap_or(WgerSearchResponse { suggestions: vec![] });
+
+    let suggestions: Vec<_> = search_resp.suggestions.into_iter().take(10).collect();
+    let mut exercises = Vec::new();
+
+    for s in suggestions {
+        let image_url = s.data.image.map(|img| {
+            if img.starts_with("http") {
+                img
+            } else {
+                format!("https://wger.de{}", img)
+            }
+        });
+
+        let (primary_muscles, secondary_muscles, equipment) = fetch_wger_details(s.data.base_id).await;
+
+        exercises.push(WgerExercise {
+            id: s.data.id,
+            base_id: s.data.base_id,
+            name: s.data.name,
+            primary_muscles,
+            secondary_muscles,
+            image_url,
+            equipment,
+        });
+    }
+
+    Ok(exercises)
+}
@@ -0,0 +1,12 @@
+-- Settings synced per user since the plate calculator, weight units,
+-- strength total lifts, E1RM formula and athlete profile.
+-- Every column is nullable: the app upserts one setting at a time.
+
+alter table public.user_settings
+    add column if not exists bar_weight double precision,
+    add column if not exists plate_inventory jsonb,
+    add column if not exists weight_unit text,
+    add column if not exists big_lifts jsonb,
+    add column if not exists e1rm_formula text,
+    add column if not exists sex text,
+    add column if not exists birth_year integer;